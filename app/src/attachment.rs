@@ -0,0 +1,123 @@
+use crate::state::DayState;
+use slack::{FileUpload, PostedMessage, SlackClient, SlackMessage};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use todoist::Todo;
+
+/// Replace the body of the daily message with a short summary, as the full list is attached
+pub fn summarize_message(message: SlackMessage, date: &str, todos: &[Todo]) -> SlackMessage {
+    let done = todos.iter().filter(|todo| todo.checked).count();
+    let open = todos.len() - done;
+
+    let mut summary = SlackMessage::new(format!(
+        "📅 *Daily Todos - {}*\n\n*{} open*, {} done · the full list is in the thread",
+        date, open, done
+    ));
+    summary.fallback_text = message.fallback_text.or_else(|| {
+        Some(format!(
+            "Daily Todos - {}: {} open, {} done",
+            date, open, done
+        ))
+    });
+    summary
+}
+
+/// The file's content and the message it goes under, so unchanged files aren't uploaded again
+fn upload_hash(content: &str, posted: &PostedMessage) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    posted.channel.hash(&mut hasher);
+    posted.ts.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Share the daily markdown file in the thread of the daily message, replacing the previous upload
+///
+/// Nothing is uploaded when neither the file nor the message changed since the last upload.
+pub async fn upload_daily_file(
+    client: &SlackClient,
+    file_path: &Path,
+    posted: &PostedMessage,
+    date: &str,
+    state: &mut DayState,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(file_path)?;
+    let hash = upload_hash(&content, posted);
+    if state.file_id.is_some() && state.file_hash == Some(hash) {
+        return Ok(());
+    }
+
+    let upload = FileUpload::new(format!("{}.md", date), content)
+        .with_title(format!("Daily Todos - {}", date))
+        .with_snippet_type("markdown");
+    let file_id = client
+        .upload_file(&upload, &posted.channel, Some(&posted.ts))
+        .await?;
+    println!(
+        "📎 Attached today's file to the Slack message ({})",
+        file_id
+    );
+
+    if let Some(previous_file_id) = state.file_id.take().filter(|id| *id != file_id) {
+        if let Err(e) = client.delete_file(&previous_file_id).await {
+            eprintln!(
+                "⚠️  Warning: Could not delete previous upload {}: {}",
+                previous_file_id, e
+            );
+        }
+    }
+
+    state.file_id = Some(file_id);
+    state.file_hash = Some(hash);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn test_summarize_message() {
+        let todos: Vec<Todo> = [false, true]
+            .into_iter()
+            .map(|checked| Todo {
+                checked,
+                ..test_support::todo("1", "Task")
+            })
+            .collect();
+
+        let summary = summarize_message(SlackMessage::new("- [ ] Task"), "2025-07-07", &todos);
+        assert_eq!(
+            summary.text,
+            "📅 *Daily Todos - 2025-07-07*\n\n*1 open*, 1 done · the full list is in the thread"
+        );
+        assert_eq!(
+            summary.fallback_text.as_deref(),
+            Some("Daily Todos - 2025-07-07: 1 open, 1 done")
+        );
+
+        let custom = SlackMessage::new("- [ ] Task").with_fallback_text("Custom");
+        let summary = summarize_message(custom, "2025-07-07", &todos);
+        assert_eq!(summary.fallback_text.as_deref(), Some("Custom"));
+    }
+
+    #[test]
+    fn test_upload_hash() {
+        let posted = PostedMessage {
+            channel: "C1".to_string(),
+            ts: "1.1".to_string(),
+        };
+        let hash = upload_hash("- [ ] Task", &posted);
+        assert_eq!(hash, upload_hash("- [ ] Task", &posted));
+        assert_ne!(hash, upload_hash("- [x] Task", &posted));
+
+        let reposted = PostedMessage {
+            ts: "2.2".to_string(),
+            ..posted
+        };
+        assert_ne!(hash, upload_hash("- [ ] Task", &reposted));
+    }
+}
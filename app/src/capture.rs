@@ -0,0 +1,186 @@
+use crate::{Config, home, in_background, sync_and_post};
+use slack::{Event, EventCallback, ReactionItem, SlackClient};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use todoist::{NewTask, TodoistClient};
+
+/// Reaction that turns a message into a task when none is configured
+pub const DEFAULT_TODO_REACTION: &str = "heavy_plus_sign";
+
+/// How many event IDs are remembered to recognize retries
+const SEEN_EVENTS: usize = 200;
+
+/// IDs of the events handled most recently
+static SEEN: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Whether this is the first delivery of an event, remembering its ID if so
+///
+/// Slack retries an event when it didn't get an answer in time, so the same ID
+/// can arrive again after it was handled. Events without an ID are always handled.
+fn first_delivery(event_id: Option<&str>) -> bool {
+    let Some(event_id) = event_id else {
+        return true;
+    };
+    let mut seen = SEEN.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if seen.iter().any(|seen| seen == event_id) {
+        return false;
+    }
+    if seen.len() == SEEN_EVENTS {
+        seen.pop_front();
+    }
+    seen.push_back(event_id.to_string());
+    true
+}
+
+/// Act on an Events API callback coming from either the HTTP endpoint or Socket Mode
+pub fn handle_event(config: Arc<Config>, callback: EventCallback) {
+    if !first_delivery(callback.event_id.as_deref()) {
+        return;
+    }
+
+    if let Event::AppHomeOpened(opened) = callback.event {
+        if opened.tab == "home" {
+            home::handle_home_opened(config, opened.user);
+        }
+        return;
+    }
+
+    in_background(async move {
+        if let Err(e) = capture_task(&config, callback.event).await {
+            eprintln!("❌ Error capturing todo from Slack: {}", e);
+        }
+    });
+}
+
+/// Create a Todoist task from a "todo:" mention or a todo reaction
+async fn capture_task(config: &Config, event: Event) -> Result<(), Box<dyn std::error::Error>> {
+    let slack_client = SlackClient::with_bot_token(config.slack_bot_token.clone())?;
+
+    let (content, channel, ts) = match event {
+        Event::AppMention(mention) => match extract_todo_text(&mention.text) {
+            Some(content) => (content, mention.channel, mention.ts),
+            None => return Ok(()),
+        },
+        Event::ReactionAdded(reaction) => {
+            let todo_reaction = config
+                .todo_reaction
+                .as_deref()
+                .unwrap_or(DEFAULT_TODO_REACTION)
+                .trim_matches(':');
+            let ReactionItem::Message { channel, ts } = reaction.item else {
+                return Ok(());
+            };
+            if reaction.reaction != todo_reaction {
+                return Ok(());
+            }
+
+            match slack_client.get_message_text(&channel, &ts).await? {
+                Some(text) => match task_content_from_message(&text) {
+                    Some(content) => (content, channel, ts),
+                    None => return Ok(()),
+                },
+                None => return Err(format!("Message {} not found in {}", ts, channel).into()),
+            }
+        }
+        Event::AppHomeOpened(_) | Event::Unsupported => return Ok(()),
+    };
+
+    let permalink = slack_client.get_permalink(&channel, &ts).await?;
+
+    let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+    let task = NewTask {
+        content,
+        description: Some(permalink),
+        due_string: Some("today".to_string()),
+        ..NewTask::default()
+    };
+    let todo = client.add_task(&task).await?;
+    println!("➕ Captured todo from Slack: {}", todo.content);
+
+    sync_and_post(config).await
+}
+
+/// Get the task text out of a mention like `<@U0BOT> todo: Review the release notes`
+fn extract_todo_text(text: &str) -> Option<String> {
+    let text = strip_mentions(text);
+    let prefix = text.get(..5)?;
+    if !prefix.eq_ignore_ascii_case("todo:") {
+        return None;
+    }
+
+    let content = text[5..].trim();
+    (!content.is_empty()).then(|| content.to_string())
+}
+
+/// Use the first line of a reacted-to message as the task content
+fn task_content_from_message(text: &str) -> Option<String> {
+    strip_mentions(text)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Remove user mentions (`<@U123>`) and surrounding whitespace
+fn strip_mentions(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("<@") {
+        result.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_delivery() {
+        assert!(first_delivery(Some("Ev-first-delivery")));
+        assert!(!first_delivery(Some("Ev-first-delivery")));
+        assert!(first_delivery(None));
+        assert!(first_delivery(None));
+    }
+
+    #[test]
+    fn test_extract_todo_text() {
+        assert_eq!(
+            extract_todo_text("<@U0BOT> todo: Review the release notes"),
+            Some("Review the release notes".to_string())
+        );
+        assert_eq!(
+            extract_todo_text("<@U0BOT>   TODO:ship it  "),
+            Some("ship it".to_string())
+        );
+        assert_eq!(extract_todo_text("<@U0BOT> hello there"), None);
+        assert_eq!(extract_todo_text("<@U0BOT> todo:   "), None);
+        assert_eq!(extract_todo_text("<@U0BOT>"), None);
+    }
+
+    #[test]
+    fn test_task_content_from_message() {
+        assert_eq!(
+            task_content_from_message("<@U123> can you look at the failing build?\nThanks!"),
+            Some("can you look at the failing build?".to_string())
+        );
+        assert_eq!(task_content_from_message("  \n  "), None);
+    }
+
+    #[test]
+    fn test_strip_mentions() {
+        assert_eq!(strip_mentions("<@U1> hi <@U2> there"), "hi  there");
+        assert_eq!(strip_mentions("no mentions"), "no mentions");
+        assert_eq!(strip_mentions("broken <@U1"), "broken <@U1");
+    }
+}
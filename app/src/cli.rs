@@ -0,0 +1,365 @@
+use crate::destinations;
+use crate::digest::Period;
+use crate::render;
+use crate::state::DayState;
+use crate::{Config, current_date, get_todos_directory, parse_existing_markdown, timezone};
+use chrono::NaiveDate;
+use clap::{ArgAction, Parser, Subcommand};
+use slack::{SlackClient, WebhookClient};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use todoist::TodoistClient;
+
+/// Sync Todoist todos into a daily markdown file and post them to Slack
+#[derive(Debug, Parser)]
+#[command(name = "slaist", version)]
+pub struct Cli {
+    /// Config file to use instead of ~/slaist/config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Act on this day (YYYY-MM-DD) instead of today
+    #[arg(long, global = true, value_name = "DATE")]
+    pub date: Option<NaiveDate>,
+
+    /// Print more details, like the todos parsed from the markdown file
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// What to do, `run` if left out
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    /// Fetch todos and update the daily markdown file, without posting
+    Sync,
+    /// Post or update the Slack message from the daily markdown file
+    Post,
+    /// Sync the markdown file, then post it
+    Run,
+    /// Keep syncing and posting on an interval until stopped
+    Watch {
+        /// Seconds between syncs, overrides `watch_interval`
+        #[arg(long, value_name = "SECONDS")]
+        interval: Option<u64>,
+    },
+    /// Show what has been synced and posted for the day
+    Status,
+    /// Create a config file with default values
+    Init {
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check the configuration and the Todoist and Slack tokens
+    Doctor,
+    /// Print the markdown file of a day
+    Show {
+        /// Day to show (YYYY-MM-DD), defaults to `--date` or today
+        date: Option<NaiveDate>,
+    },
+    /// Serve checkboxes, slash commands and events over HTTP
+    Serve,
+    /// Receive Slack events over Socket Mode
+    Socket,
+    /// Delete the day's Slack messages
+    Delete,
+    /// Print links to the day's Slack messages
+    Permalink,
+    /// Delete the day's Slack messages and post fresh ones
+    Repost,
+    /// Write a digest of the week or month of daily files
+    Digest {
+        /// Period around `--date` or today to cover
+        #[arg(value_enum, default_value_t)]
+        period: Period,
+        /// Post the digest to Slack as well
+        #[arg(long)]
+        post: bool,
+    },
+    /// Post a Yesterday / Today / Blockers standup
+    Standup {
+        /// Reply in the thread of the day's message, overrides `standup.thread`
+        #[arg(long)]
+        thread: bool,
+    },
+}
+
+impl Command {
+    /// Whether the command only reads local files, so it shouldn't wait on the network
+    pub fn is_offline(&self) -> bool {
+        matches!(
+            self,
+            Command::Status | Command::Show { .. } | Command::Digest { post: false, .. }
+        )
+    }
+}
+
+/// Turn the result of a command into an exit code, printing the error if there is one
+pub fn report(result: Result<(), Box<dyn Error>>, context: &str) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("❌ {}: {}", context, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn markdown_path(config: &Config) -> (String, PathBuf) {
+    let date_str = current_date(config).format("%Y-%m-%d").to_string();
+    let path = get_todos_directory(config).join(format!("{}.md", date_str));
+    (date_str, path)
+}
+
+/// Create a config file with default values
+pub fn init(config_path: &Path, force: bool) -> Result<(), Box<dyn Error>> {
+    if config_path.exists() && !force {
+        return Err(format!(
+            "{} already exists, pass --force to overwrite it",
+            config_path.display()
+        )
+        .into());
+    }
+
+    Config::write_default(config_path)?;
+    println!(
+        "📝 Created default config file at: {}",
+        config_path.display()
+    );
+    println!("⚠️  Please edit the config file and add your API tokens:");
+    println!("   - todoist_api_token: Get from https://todoist.com/prefs/integrations");
+    println!("   - slack_bot_token: Get from your Slack app settings");
+    println!("   Then run `slaist doctor` to check your setup.");
+    Ok(())
+}
+
+/// Print the markdown file of the day
+pub fn show(config: &Config) -> Result<(), Box<dyn Error>> {
+    let (date_str, path) = markdown_path(config);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("No todo file for {} at {}: {}", date_str, path.display(), e))?;
+    print!("{}", content);
+    Ok(())
+}
+
+/// Print what has been synced and posted for the day
+pub fn status(config: &Config) -> Result<(), Box<dyn Error>> {
+    let (date_str, path) = markdown_path(config);
+    let state = DayState::load(config, &date_str);
+    println!("📅 {}", date_str);
+
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            let (todos, notes) = parse_existing_markdown(&content);
+            let done = todos.iter().filter(|(_, done)| *done).count();
+            println!("📁 {}", path.display());
+            println!("   Open: {}", todos.len() - done);
+            println!("   Completed: {}", done);
+            if notes.is_some() {
+                println!("   Has notes");
+            }
+        }
+        Err(_) => println!("📁 Not synced yet, {} doesn't exist", path.display()),
+    }
+
+    match state.last_sync {
+        Some(last_sync) => println!(
+            "🔄 Last synced {}",
+            last_sync
+                .with_timezone(&timezone(config))
+                .format("%Y-%m-%d %H:%M:%S %Z")
+        ),
+        None => println!("🔄 Never synced from Todoist"),
+    }
+
+    println!("📢 Slack:");
+    for destination in destinations::configured(config) {
+        match state.messages.get(&destination.key()) {
+            Some(message) => println!("   {}: posted ({})", destination.key(), message.ts),
+            None => println!("   {}: not posted", destination.key()),
+        }
+    }
+    if let Some(file_id) = &state.file_id {
+        println!("   Attached file: {}", file_id);
+    }
+    if let Some(sent) = &state.webhook_sent {
+        println!(
+            "   Webhook: {} open, {} completed announced",
+            sent.open.len(),
+            sent.done.len()
+        );
+    }
+    Ok(())
+}
+
+/// Print the outcome of one check, returning whether it passed
+fn check(name: &str, result: Result<String, Box<dyn Error>>) -> bool {
+    match result {
+        Ok(detail) => {
+            println!("✅ {}: {}", name, detail);
+            true
+        }
+        Err(e) => {
+            println!("❌ {}: {}", name, e);
+            false
+        }
+    }
+}
+
+/// Check the configuration and the Todoist and Slack tokens
+pub async fn doctor(config: &Config, config_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut healthy = check("Config", Ok(config_path.display().to_string()));
+
+    check("Timezone", Ok(timezone(config).to_string()));
+
+    if let Some(templates) = &config.templates {
+        let paths = [
+            ("Markdown template", &templates.markdown),
+            ("Slack template", &templates.slack),
+        ];
+        for (name, path) in paths {
+            if let Some(path) = path {
+                healthy &= check(name, render::check_template(path));
+            }
+        }
+    }
+
+    let todos_directory = get_todos_directory(config);
+    healthy &= check(
+        "Todos directory",
+        fs::create_dir_all(&todos_directory)
+            .map(|()| todos_directory.display().to_string())
+            .map_err(Into::into),
+    );
+
+    let todoist = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+    healthy &= check(
+        "Todoist token",
+        todoist
+            .get_projects()
+            .await
+            .map(|projects| format!("works, {} projects", projects.len()))
+            .map_err(Into::into),
+    );
+
+    match config
+        .slack_webhook_url
+        .as_deref()
+        .filter(|url| !url.is_empty())
+    {
+        Some(url) => {
+            healthy &= check(
+                "Slack webhook URL",
+                WebhookClient::new(url)
+                    .map(|_| "looks valid, posting isn't tested".to_string())
+                    .map_err(Into::into),
+            );
+        }
+        None => {
+            let result = match SlackClient::with_bot_token(config.slack_bot_token.clone()) {
+                Ok(client) => client
+                    .auth_test()
+                    .await
+                    .map(|auth| format!("works, posting as {} in {}", auth.user, auth.team))
+                    .map_err(Into::into),
+                Err(e) => Err(e.into()),
+            };
+            healthy &= check("Slack bot token", result);
+
+            let keys: Vec<String> = destinations::configured(config)
+                .iter()
+                .map(destinations::Destination::key)
+                .collect();
+            check("Slack destinations", Ok(keys.join(", ")));
+        }
+    }
+
+    if let Some(token) = config
+        .slack_user_token
+        .as_deref()
+        .filter(|token| !token.is_empty())
+    {
+        let result = match SlackClient::with_user_token(token.to_string()) {
+            Ok(client) => client
+                .auth_test()
+                .await
+                .map(|auth| format!("works for {}", auth.user))
+                .map_err(Into::into),
+            Err(e) => Err(e.into()),
+        };
+        healthy &= check("Slack user token", result);
+    }
+
+    if healthy {
+        Ok(())
+    } else {
+        Err("Some checks failed".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        let cli = Cli::try_parse_from(["slaist"]).unwrap();
+        assert_eq!(cli.command, None);
+
+        let cli = Cli::try_parse_from([
+            "slaist",
+            "post",
+            "--config",
+            "/tmp/config.toml",
+            "--date",
+            "2025-07-07",
+            "-vv",
+        ])
+        .unwrap();
+        assert_eq!(cli.command, Some(Command::Post));
+        assert_eq!(cli.config, Some(PathBuf::from("/tmp/config.toml")));
+        assert_eq!(cli.date, NaiveDate::from_ymd_opt(2025, 7, 7));
+        assert_eq!(cli.verbose, 2);
+
+        let cli = Cli::try_parse_from(["slaist", "show", "2025-07-01"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Show {
+                date: NaiveDate::from_ymd_opt(2025, 7, 1)
+            })
+        );
+
+        assert!(Command::Status.is_offline());
+        assert!(!Command::Post.is_offline());
+        let digest = |args: &[&str]| {
+            Cli::try_parse_from(args)
+                .unwrap()
+                .command
+                .unwrap()
+                .is_offline()
+        };
+        assert!(digest(&["slaist", "digest"]));
+        assert!(!digest(&["slaist", "digest", "--post"]));
+
+        assert!(Cli::try_parse_from(["slaist", "--date", "yesterday"]).is_err());
+        assert!(Cli::try_parse_from(["slaist", "unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_init_refuses_to_overwrite() {
+        let directory = tempfile::tempdir().unwrap();
+        let config_path = directory.path().join("slaist").join("config.toml");
+
+        init(&config_path, false).unwrap();
+        assert!(init(&config_path, false).is_err());
+        init(&config_path, true).unwrap();
+
+        let config: Config = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config.slack_channel.as_deref(), Some("#general"));
+    }
+}
@@ -0,0 +1,428 @@
+use crate::Config;
+use serde::{Deserialize, Serialize};
+use slack::{ApiErrorCode, PostedMessage, SlackClient, SlackError, SlackMessage, WebhookClient};
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Where the daily message is posted
+///
+/// Exactly one of `channel` and `user` should be set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Destination {
+    /// Channel name or ID
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// User ID to send a direct message to
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Post as a reply in the thread of this message in `channel`
+    #[serde(default)]
+    pub thread_ts: Option<String>,
+}
+
+impl Destination {
+    /// Identifies the destination in the stored messages of a day
+    pub fn key(&self) -> String {
+        match (&self.user, &self.channel, &self.thread_ts) {
+            (Some(user), _, _) => format!("user:{}", user),
+            (None, Some(channel), Some(thread_ts)) => {
+                format!("channel:{}/thread:{}", channel, thread_ts)
+            }
+            (None, Some(channel), None) => format!("channel:{}", channel),
+            (None, None, _) => String::new(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match (&self.channel, &self.user) {
+            (Some(channel), None) => !channel.is_empty(),
+            (None, Some(user)) => !user.is_empty() && self.thread_ts.is_none(),
+            _ => false,
+        }
+    }
+}
+
+/// The configured destinations, or `slack_channel` if there are none
+pub fn configured(config: &Config) -> Vec<Destination> {
+    let destinations: Vec<Destination> = config
+        .slack_destinations
+        .iter()
+        .flatten()
+        .filter(|destination| {
+            let valid = destination.is_valid();
+            if !valid {
+                eprintln!(
+                    "⚠️  Warning: Skipping Slack destination {:?}, set either channel or user (threads need a channel)",
+                    destination
+                );
+            }
+            valid
+        })
+        .cloned()
+        .collect();
+
+    if !destinations.is_empty() {
+        return destinations;
+    }
+    vec![Destination {
+        channel: Some(
+            config
+                .slack_channel
+                .clone()
+                .unwrap_or_else(|| "#general".to_string()),
+        ),
+        ..Destination::default()
+    }]
+}
+
+/// Messages posted today, keyed by [`Destination::key`]
+pub type PostedMessages = BTreeMap<String, PostedMessage>;
+
+/// Print hints for common reasons posting to a destination fails
+fn print_error_guidance(error: &SlackError, destination: &str) {
+    match error {
+        SlackError::HttpError(_) => {
+            eprintln!("   This might be a network connectivity issue.");
+        }
+        SlackError::RateLimited { .. } => {
+            eprintln!("   Slack is rate limiting requests, try again later.");
+        }
+        SlackError::ApiError(code) => {
+            eprintln!("   Slack API error: {}", code);
+            if code.is_auth_error() {
+                eprintln!("   Check your Slack bot token.");
+            }
+            match code {
+                ApiErrorCode::ChannelNotFound => {
+                    eprintln!(
+                        "   Check the channel of {} in ~/slaist/config.toml",
+                        destination
+                    );
+                }
+                ApiErrorCode::NotInChannel => {
+                    eprintln!("   Invite the bot to {} first.", destination);
+                }
+                ApiErrorCode::CantUpdateMessage => {
+                    eprintln!(
+                        "   → This usually means the bot doesn't have permission to update this message"
+                    );
+                    eprintln!("   → Or the message was posted by a different bot/user");
+                }
+                ApiErrorCode::MessageNotFound => {
+                    eprintln!("   → The message with this ID no longer exists");
+                }
+                _ => {}
+            }
+        }
+        SlackError::ConfigError(_) => {
+            eprintln!("   Check your slack_bot_token in ~/slaist/config.toml");
+        }
+        _ => {}
+    }
+}
+
+/// Post a new copy of the message to a destination
+async fn post_new(
+    client: &SlackClient,
+    destination: &Destination,
+    message: &SlackMessage,
+) -> Result<PostedMessage, SlackError> {
+    let channel = match (&destination.user, &destination.channel) {
+        (Some(user), _) => client.open_dm(user).await?,
+        (None, Some(channel)) => channel.clone(),
+        (None, None) => {
+            return Err(SlackError::ConfigError(
+                "Destination has no channel or user".to_string(),
+            ));
+        }
+    };
+
+    match &destination.thread_ts {
+        Some(thread_ts) => {
+            let reply = message.clone().in_thread(thread_ts.clone());
+            client.post_message(&reply, &channel).await
+        }
+        None => client.post_message(message, &channel).await,
+    }
+}
+
+/// Whether a failed update means the message is gone, so a new copy should be posted
+///
+/// Any other failure leaves the existing message in place, as posting a new one
+/// could duplicate it.
+fn should_repost(error: &SlackError) -> bool {
+    matches!(error, SlackError::ApiError(ApiErrorCode::MessageNotFound))
+}
+
+/// Whether a tracked channel is an ID rather than a name, like the ones migrated
+/// from metadata comments of earlier versions
+fn is_channel_id(channel: &str) -> bool {
+    !channel.is_empty()
+        && channel
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// ID of the channel a message was posted in
+///
+/// Messages tracked by channel name are looked up by ID first, as Slack only
+/// accepts channel IDs for existing messages.
+pub async fn channel_id(
+    client: &SlackClient,
+    posted: &PostedMessage,
+) -> Result<String, SlackError> {
+    if is_channel_id(&posted.channel) {
+        return Ok(posted.channel.clone());
+    }
+    client.find_channel_id(&posted.channel).await
+}
+
+/// Update a posted copy of the message
+async fn update(
+    client: &SlackClient,
+    message: &SlackMessage,
+    existing: &PostedMessage,
+) -> Result<PostedMessage, SlackError> {
+    let channel = channel_id(client, existing).await?;
+    client.update_message(message, &channel, &existing.ts).await
+}
+
+/// Update the existing copy of the message at a destination, or post a new one
+///
+/// A new copy is only posted when there is none yet or the existing one was deleted.
+async fn deliver(
+    client: &SlackClient,
+    destination: &Destination,
+    message: &SlackMessage,
+    existing: Option<&PostedMessage>,
+) -> Result<PostedMessage, SlackError> {
+    let key = destination.key();
+
+    if let Some(existing) = existing {
+        println!("🔄 Updating existing message in {}...", key);
+        match update(client, message, existing).await {
+            Ok(updated) => return Ok(updated),
+            Err(e) if should_repost(&e) => {
+                eprintln!("❌ Error updating Slack message in {}: {}", key, e);
+                eprintln!("   Message ID: {}", existing.ts);
+                print_error_guidance(&e, &key);
+                println!("🔄 Attempting to post as new message...");
+            }
+            Err(e) => return Err(e),
+        }
+    } else {
+        println!("🚀 Sending new message to {}...", key);
+    }
+
+    post_new(client, destination, message).await
+}
+
+/// Post or update the message at every destination, returning the keys of those that failed
+///
+/// A failing destination doesn't affect the others; its previous message, if
+/// any, stays in `posted` so the next sync tries to update it again.
+pub async fn deliver_all(
+    client: &SlackClient,
+    destinations: &[Destination],
+    message: &SlackMessage,
+    posted: &mut PostedMessages,
+) -> Vec<String> {
+    let mut failed = Vec::new();
+    for destination in destinations {
+        let key = destination.key();
+        match deliver(client, destination, message, posted.get(&key)).await {
+            Ok(message) => {
+                println!("✅ Successfully sent today's todos to {}!", key);
+                println!("   Message ID: {}", message.ts);
+                posted.insert(key, message);
+            }
+            Err(e) => {
+                eprintln!("❌ Error posting to {}: {}", key, e);
+                print_error_guidance(&e, &key);
+                failed.push(key);
+            }
+        }
+    }
+    failed
+}
+
+/// The bot client, with the configured icons and allowed mentions
+fn bot_client(config: &Config) -> Result<SlackClient, SlackError> {
+    Ok(SlackClient::with_bot_token(config.slack_bot_token.clone())?
+        .with_icons(config.slack_icons.clone().unwrap_or_default())
+        .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default()))
+}
+
+/// Error naming the destinations that failed, if any did
+fn check_failed(failed: Vec<String>) -> Result<(), Box<dyn Error>> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(format!("Could not post to {}", failed.join(", ")).into())
+}
+
+/// Post the message as a message of its own, through the incoming webhook if one
+/// is configured and to every configured destination otherwise
+///
+/// A failing destination doesn't stop the others, but the post fails if any did.
+pub async fn post_to_all(config: &Config, message: &SlackMessage) -> Result<(), Box<dyn Error>> {
+    if let Some(webhook_url) = config
+        .slack_webhook_url
+        .as_deref()
+        .filter(|url| !url.is_empty())
+    {
+        let client = WebhookClient::new(webhook_url)?
+            .with_icons(config.slack_icons.clone().unwrap_or_default())
+            .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default());
+        client.send(message).await?;
+        println!("✅ Posted the message through the webhook");
+        return Ok(());
+    }
+
+    let client = bot_client(config)?;
+    let mut failed = Vec::new();
+    for destination in configured(config) {
+        let key = destination.key();
+        match post_new(&client, &destination, message).await {
+            Ok(_) => println!("✅ Posted the message to {}", key),
+            Err(e) => {
+                eprintln!("❌ Error posting to {}: {}", key, e);
+                print_error_guidance(&e, &key);
+                failed.push(key);
+            }
+        }
+    }
+    check_failed(failed)
+}
+
+/// Reply with the message in the thread of every posted copy of the day's message
+///
+/// A failing thread doesn't stop the others, but the reply fails if any did.
+pub async fn reply_to_all(
+    config: &Config,
+    posted: &PostedMessages,
+    message: &SlackMessage,
+) -> Result<(), Box<dyn Error>> {
+    if posted.is_empty() {
+        return Err("No Slack message has been posted today to reply to".into());
+    }
+
+    let client = bot_client(config)?;
+    let mut failed = Vec::new();
+    for (key, existing) in posted {
+        let reply = message.clone().in_thread(existing.ts.clone());
+        let replied = match channel_id(&client, existing).await {
+            Ok(channel) => client.post_message(&reply, &channel).await,
+            Err(e) => Err(e),
+        };
+        match replied {
+            Ok(_) => println!("✅ Replied in the thread in {}", key),
+            Err(e) => {
+                eprintln!("❌ Error replying in the thread in {}: {}", key, e);
+                print_error_guidance(&e, key);
+                failed.push(key.clone());
+            }
+        }
+    }
+    check_failed(failed)
+}
+
+/// Update every posted copy of the message, without ever posting a new one
+///
+/// Copies that can't be updated are logged and dropped from `posted`.
+pub async fn update_all(client: &SlackClient, message: &SlackMessage, posted: &mut PostedMessages) {
+    for (key, existing) in std::mem::take(posted) {
+        match update(client, message, &existing).await {
+            Ok(updated) => {
+                println!("✅ Updated the message in {}", key);
+                posted.insert(key, updated);
+            }
+            Err(e) => {
+                eprintln!("❌ Error updating the message in {}: {}", key, e);
+                print_error_guidance(&e, &key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(name: &str) -> Destination {
+        Destination {
+            channel: Some(name.to_string()),
+            ..Destination::default()
+        }
+    }
+
+    #[test]
+    fn test_destination_keys() {
+        assert_eq!(channel("#general").key(), "channel:#general");
+        assert_eq!(
+            Destination {
+                thread_ts: Some("1.2".to_string()),
+                ..channel("C123")
+            }
+            .key(),
+            "channel:C123/thread:1.2"
+        );
+        assert_eq!(
+            Destination {
+                user: Some("U123".to_string()),
+                ..Destination::default()
+            }
+            .key(),
+            "user:U123"
+        );
+    }
+
+    #[test]
+    fn test_only_deleted_messages_are_reposted() {
+        assert!(should_repost(&SlackError::ApiError(
+            ApiErrorCode::MessageNotFound
+        )));
+        assert!(!should_repost(&SlackError::ApiError(
+            ApiErrorCode::ChannelNotFound
+        )));
+        assert!(!should_repost(&SlackError::ApiError(
+            ApiErrorCode::CantUpdateMessage
+        )));
+        assert!(!should_repost(&SlackError::RateLimited {
+            retry_after: None
+        }));
+    }
+
+    #[test]
+    fn test_is_channel_id() {
+        assert!(is_channel_id("C0123456789"));
+        assert!(is_channel_id("D123"));
+        assert!(!is_channel_id("#general"));
+        assert!(!is_channel_id("general"));
+        assert!(!is_channel_id(""));
+    }
+
+    #[test]
+    fn test_configured_destinations() {
+        let config = Config {
+            slack_channel: Some("#todos".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(configured(&config), vec![channel("#todos")]);
+
+        let config = Config {
+            slack_destinations: Some(vec![
+                channel("#team"),
+                Destination {
+                    user: Some("U123".to_string()),
+                    ..Destination::default()
+                },
+                // Invalid: neither channel nor user
+                Destination::default(),
+            ]),
+            ..config
+        };
+        let keys: Vec<String> = configured(&config).iter().map(Destination::key).collect();
+        assert_eq!(keys, ["channel:#team", "user:U123"]);
+    }
+}
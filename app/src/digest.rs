@@ -0,0 +1,284 @@
+use crate::state::DayState;
+use crate::{Config, current_date, destinations, get_todos_directory, parse_existing_markdown};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use clap::ValueEnum;
+use slack::SlackMessage;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+
+/// Most entries listed for carried todos and projects
+const TOP: usize = 5;
+/// Most notes lines quoted per day
+const NOTES_PER_DAY: usize = 3;
+
+/// Stretch of days a digest covers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Period {
+    /// The week, Monday to Sunday, of the day
+    #[default]
+    Week,
+    /// The calendar month of the day
+    Month,
+}
+
+impl Period {
+    /// First and last day of the period around `date`
+    fn range(self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Period::Week => {
+                let monday = date - Days::new(date.weekday().num_days_from_monday().into());
+                (monday, monday + Days::new(6))
+            }
+            Period::Month => {
+                let first = date.with_day(1).unwrap_or(date);
+                (first, first + Months::new(1) - Days::new(1))
+            }
+        }
+    }
+
+    /// Name of the period around `date`, also used for the digest's file name
+    fn name(self, date: NaiveDate) -> String {
+        match self {
+            Period::Week => date.format("%G-W%V").to_string(),
+            Period::Month => date.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// What a digest takes from one day's file and state
+#[derive(Debug, Default, PartialEq)]
+struct Day {
+    date: NaiveDate,
+    /// Completed todos and the project they were completed in, if the state has it
+    completed: Vec<(String, Option<String>)>,
+    /// Open todos and the number of days each was carried over
+    carried: BTreeMap<String, u32>,
+    notes: Option<String>,
+}
+
+impl Day {
+    fn parse(date: NaiveDate, markdown: &str, state: &DayState) -> Self {
+        let (todos, notes) = parse_existing_markdown(markdown);
+        let completed = todos
+            .into_iter()
+            .filter(|(_, done)| *done)
+            .map(|(content, _)| {
+                let project = state
+                    .task_ids
+                    .get(&content)
+                    .and_then(|id| state.completed_projects.get(id))
+                    .cloned();
+                (content, project)
+            })
+            .collect();
+        Self {
+            date,
+            completed,
+            carried: state.carried.clone(),
+            notes,
+        }
+    }
+}
+
+/// Read the days in a range that have a markdown file
+fn read_days(config: &Config, from: NaiveDate, to: NaiveDate) -> Vec<Day> {
+    let directory = get_todos_directory(config);
+    from.iter_days()
+        .take_while(|date| *date <= to)
+        .filter_map(|date| {
+            let date_str = date.format("%Y-%m-%d").to_string();
+            let markdown = fs::read_to_string(directory.join(format!("{}.md", date_str))).ok()?;
+            Some(Day::parse(
+                date,
+                &markdown,
+                &DayState::load(config, &date_str),
+            ))
+        })
+        .collect()
+}
+
+/// The digest of some days as markdown
+fn build_digest(title: &str, days: &[Day]) -> String {
+    let mut content = format!("# 📊 {}\n\n## Completions per Day\n\n", title);
+    if days.is_empty() {
+        content.push_str("_No daily files in this period._\n");
+    }
+    for day in days {
+        content.push_str(&format!(
+            "- {}: {}\n",
+            day.date.format("%a %-d %b"),
+            day.completed.len()
+        ));
+    }
+    let total: usize = days.iter().map(|day| day.completed.len()).sum();
+    if !days.is_empty() {
+        content.push_str(&format!("\n{} completed over {} days\n", total, days.len()));
+    }
+
+    // The longest a todo was carried is the count on the last day it was carried
+    let mut carried: BTreeMap<&str, u32> = BTreeMap::new();
+    for day in days {
+        for (todo, count) in &day.carried {
+            let longest = carried.entry(todo).or_default();
+            *longest = (*longest).max(*count);
+        }
+    }
+    let mut carried: Vec<(&str, u32)> = carried.into_iter().collect();
+    carried.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    content.push_str("\n## Carried Over Longest\n\n");
+    if carried.is_empty() {
+        content.push_str("_Nothing was carried over._\n");
+    }
+    for (todo, count) in carried.into_iter().take(TOP) {
+        let unit = if count == 1 { "day" } else { "days" };
+        content.push_str(&format!("- {}: {} {}\n", todo, count, unit));
+    }
+
+    let mut projects: BTreeMap<&str, usize> = BTreeMap::new();
+    for (_, project) in days.iter().flat_map(|day| &day.completed) {
+        if let Some(project) = project {
+            *projects.entry(project).or_default() += 1;
+        }
+    }
+    let mut projects: Vec<(&str, usize)> = projects.into_iter().collect();
+    projects.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    content.push_str("\n## Busiest Projects\n\n");
+    if projects.is_empty() {
+        content.push_str("_No completed todos with a project._\n");
+    }
+    for (project, count) in projects.into_iter().take(TOP) {
+        content.push_str(&format!("- {}: {} completed\n", project, count));
+    }
+
+    content.push_str("\n## Notes Highlights\n");
+    let mut has_notes = false;
+    for day in days {
+        let lines: Vec<&str> = day
+            .notes
+            .iter()
+            .flat_map(|notes| notes.lines())
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .take(NOTES_PER_DAY)
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        has_notes = true;
+        content.push_str(&format!("\n### {}\n\n", day.date.format("%a %-d %b")));
+        for line in lines {
+            let line = line.strip_prefix("- ").unwrap_or(line);
+            content.push_str(&format!("- {}\n", line));
+        }
+    }
+    if !has_notes {
+        content.push_str("\n_No notes._\n");
+    }
+    content
+}
+
+/// Write the digest of the period around the day to `digests/` and optionally post it
+pub async fn run(
+    config: &Config,
+    period: Period,
+    post_to_slack: bool,
+) -> Result<(), Box<dyn Error>> {
+    let date = current_date(config);
+    let (from, to) = period.range(date);
+    let name = period.name(date);
+    let kind = match period {
+        Period::Week => "Weekly",
+        Period::Month => "Monthly",
+    };
+    let title = format!(
+        "{} Digest - {} ({} to {})",
+        kind,
+        name,
+        from.format("%Y-%m-%d"),
+        to.format("%Y-%m-%d")
+    );
+    let digest = build_digest(&title, &read_days(config, from, to));
+
+    let directory = get_todos_directory(config).join("digests");
+    fs::create_dir_all(&directory)?;
+    let file_path = directory.join(format!("{}.md", name));
+    fs::write(&file_path, &digest)?;
+    println!("📊 Wrote the digest to {}", file_path.display());
+
+    if post_to_slack {
+        destinations::post_to_all(config, &SlackMessage::new(digest)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_period_range() {
+        assert_eq!(
+            Period::Week.range(date("2025-07-09")),
+            (date("2025-07-07"), date("2025-07-13"))
+        );
+        assert_eq!(Period::Week.name(date("2025-07-09")), "2025-W28");
+        assert_eq!(
+            Period::Month.range(date("2024-02-10")),
+            (date("2024-02-01"), date("2024-02-29"))
+        );
+        assert_eq!(Period::Month.name(date("2024-02-10")), "2024-02");
+    }
+
+    #[test]
+    fn test_build_digest() {
+        let monday = "## Active Todos\n\n- [ ] Task A · 📁 Work\n\n## Completed Todos\n\n- [x] Task B · 🔴 P1 · 📁 Work › Planning\n- [x] Task C *(marked as finished)*\n---\nShipped the release\n\n- Blocked: review\n- Lunch\n- Too much\n";
+        let tuesday = "## Active Todos\n\n- [ ] Task A *(carried 1 day)*\n\n## Completed Todos\n\n- [x] Task D · 📁 Home\n- [x] Task E · 📁 Work\n";
+        let task_ids = |ids: &[(&str, &str)]| {
+            ids.iter()
+                .map(|(content, id)| (content.to_string(), id.to_string()))
+                .collect()
+        };
+        let projects = BTreeMap::from([
+            ("b".to_string(), "Work".to_string()),
+            ("d".to_string(), "Home".to_string()),
+            ("e".to_string(), "Work".to_string()),
+        ]);
+        let monday_state = DayState {
+            task_ids: task_ids(&[("Task B", "b"), ("Task C", "c")]),
+            completed_projects: projects.clone(),
+            ..DayState::default()
+        };
+        let tuesday_state = DayState {
+            carried: BTreeMap::from([("Task A".to_string(), 1)]),
+            task_ids: task_ids(&[("Task D", "d"), ("Task E", "e")]),
+            completed_projects: projects,
+            ..DayState::default()
+        };
+        let days = vec![
+            Day::parse(date("2025-07-07"), monday, &monday_state),
+            Day::parse(date("2025-07-08"), tuesday, &tuesday_state),
+        ];
+        assert_eq!(
+            days[0].completed,
+            vec![
+                ("Task B".to_string(), Some("Work".to_string())),
+                ("Task C".to_string(), None),
+            ]
+        );
+
+        assert_eq!(
+            build_digest("Weekly Digest", &days),
+            "# 📊 Weekly Digest\n\n\
+             ## Completions per Day\n\n- Mon 7 Jul: 2\n- Tue 8 Jul: 2\n\n4 completed over 2 days\n\n\
+             ## Carried Over Longest\n\n- Task A: 1 day\n\n\
+             ## Busiest Projects\n\n- Work: 2 completed\n- Home: 1 completed\n\n\
+             ## Notes Highlights\n\n### Mon 7 Jul\n\n- Shipped the release\n- Blocked: review\n- Lunch\n"
+        );
+        assert!(build_digest("Empty", &[]).contains("_No daily files in this period._\n"));
+    }
+}
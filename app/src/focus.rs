@@ -0,0 +1,150 @@
+use crate::Config;
+use slack::{SlackClient, SlackStatus};
+use todoist::Todo;
+
+/// Label that marks the task you are working on right now
+pub const DEFAULT_FOCUS_LABEL: &str = "now";
+
+/// Status emoji used while a focus task is set
+pub const DEFAULT_FOCUS_EMOJI: &str = ":dart:";
+
+/// How the Slack status should change after a sync
+#[derive(Debug, PartialEq)]
+enum StatusChange {
+    Set(SlackStatus),
+    Clear,
+}
+
+/// The first open todo carrying the focus label
+fn focus_task<'a>(todos: &'a [Todo], label: &str) -> Option<&'a Todo> {
+    todos
+        .iter()
+        .find(|todo| !todo.checked && todo.labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+}
+
+/// Decide how to change the status
+///
+/// A status with the focus emoji is considered to be set by slaist, so it is
+/// cleared once there is no focus task anymore. Any other status is left alone
+/// unless a new focus task replaces it.
+fn plan_status_change(
+    focus: Option<&Todo>,
+    current: &SlackStatus,
+    emoji: &str,
+) -> Option<StatusChange> {
+    match focus {
+        Some(todo) => {
+            let status = SlackStatus::new(&todo.content, emoji);
+            (status.text != current.text || status.emoji != current.emoji)
+                .then_some(StatusChange::Set(status))
+        }
+        None if current.emoji == emoji => Some(StatusChange::Clear),
+        None => None,
+    }
+}
+
+/// Mirror the focus task into the Slack status of the user
+///
+/// Does nothing unless `slack_user_token` is configured.
+pub async fn sync_status(
+    config: &Config,
+    todos: &[Todo],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(user_token) = config
+        .slack_user_token
+        .clone()
+        .filter(|token| !token.is_empty())
+    else {
+        return Ok(());
+    };
+
+    let label = config.focus_label.as_deref().unwrap_or(DEFAULT_FOCUS_LABEL);
+    let emoji = config
+        .focus_status_emoji
+        .as_deref()
+        .unwrap_or(DEFAULT_FOCUS_EMOJI);
+
+    let client = SlackClient::with_user_token(user_token)?;
+    let current = client.get_status().await?;
+
+    match plan_status_change(focus_task(todos, label), &current, emoji) {
+        Some(StatusChange::Set(status)) => {
+            client.set_status(&status).await?;
+            println!("🎯 Set Slack status to: {}", status.text);
+        }
+        Some(StatusChange::Clear) => {
+            client.clear_status().await?;
+            println!("🎯 Cleared Slack status, no focus task left");
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(content: &str, labels: &[&str], checked: bool) -> Todo {
+        Todo {
+            labels: test_support::labels(labels),
+            checked,
+            ..test_support::todo("1", content)
+        }
+    }
+
+    #[test]
+    fn test_focus_task() {
+        let todos = vec![
+            todo("Done focus", &["now"], true),
+            todo("Other", &["work"], false),
+            todo("Write report", &["work", "Now"], false),
+        ];
+
+        assert_eq!(
+            focus_task(&todos, "now").map(|t| t.content.as_str()),
+            Some("Write report")
+        );
+        assert!(focus_task(&todos, "later").is_none());
+    }
+
+    #[test]
+    fn test_sets_status_for_focus_task() {
+        let task = todo("Write report", &["now"], false);
+
+        assert_eq!(
+            plan_status_change(Some(&task), &SlackStatus::default(), ":dart:"),
+            Some(StatusChange::Set(SlackStatus::new(
+                "Write report",
+                ":dart:"
+            )))
+        );
+        // Already showing the task
+        assert_eq!(
+            plan_status_change(
+                Some(&task),
+                &SlackStatus::new("Write report", ":dart:"),
+                ":dart:"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clears_only_own_status() {
+        assert_eq!(
+            plan_status_change(None, &SlackStatus::new("Write report", ":dart:"), ":dart:"),
+            Some(StatusChange::Clear)
+        );
+        assert_eq!(
+            plan_status_change(None, &SlackStatus::new("Lunch", ":taco:"), ":dart:"),
+            None
+        );
+        assert_eq!(
+            plan_status_change(None, &SlackStatus::default(), ":dart:"),
+            None
+        );
+    }
+}
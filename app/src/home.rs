@@ -0,0 +1,170 @@
+use crate::state::DayState;
+use crate::{
+    Config, current_date, get_todos_directory, in_background, is_overdue, parse_existing_markdown,
+    timezone,
+};
+use chrono::NaiveDate;
+use slack::{HomeProject, HomeTab, SlackClient, SlackTask};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use todoist::{Todo, TodoistClient};
+
+/// Publish the Home tab for a user who just opened it, from the last sync
+pub fn handle_home_opened(config: Arc<Config>, user_id: String) {
+    in_background(async move {
+        if let Err(e) = publish(&config, &[user_id]).await {
+            eprintln!("❌ Error publishing Slack Home tab: {}", e);
+        }
+    });
+}
+
+/// Publish the Home tab for the users in `slack_home_users` after a sync
+pub async fn refresh(config: &Config) {
+    let users = config.slack_home_users.as_deref().unwrap_or_default();
+    if users.is_empty() {
+        return;
+    }
+    if let Err(e) = publish(config, users).await {
+        eprintln!("❌ Error publishing Slack Home tab: {}", e);
+    }
+}
+
+/// Publish the Home tab built from the todos of the last sync and today's markdown file
+///
+/// Syncing is left to `run` and `watch`, so opening the tab doesn't touch Todoist
+/// or the file.
+async fn publish(config: &Config, users: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let today = current_date(config);
+    let date_str = today.format("%Y-%m-%d").to_string();
+    let state = DayState::load(config, &date_str);
+    let todos = &state.todos;
+
+    // Completions come from the markdown file, which also keeps todos finished earlier today
+    let file_path = get_todos_directory(config).join(format!("{}.md", date_str));
+    let (existing_todos, _) = parse_existing_markdown(&fs::read_to_string(&file_path)?);
+    let completed = existing_todos
+        .into_iter()
+        .filter(|(_, done)| *done)
+        .map(|(content, _)| content)
+        .collect();
+
+    let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+    let project_names: HashMap<String, String> = match client.get_projects().await {
+        Ok(projects) => projects.into_iter().map(|p| (p.id, p.name)).collect(),
+        Err(e) => {
+            eprintln!("⚠️  Warning: Could not fetch Todoist projects: {}", e);
+            HashMap::new()
+        }
+    };
+
+    let mut home = build_home_tab(todos, &project_names, completed, today);
+    home.title = format!("📅 Daily Todos - {}", date_str);
+    home.subtitle = state.last_sync.map(|last_sync| {
+        format!(
+            "Last synced {}",
+            last_sync
+                .with_timezone(&timezone(config))
+                .format("%H:%M %Z")
+        )
+    });
+
+    let slack_client = SlackClient::with_bot_token(config.slack_bot_token.clone())?
+        .with_icons(config.slack_icons.clone().unwrap_or_default())
+        .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default());
+    for user in users {
+        slack_client.publish_home(user, &home).await?;
+    }
+    println!("🏠 Published Slack Home tab for {} user(s)", users.len());
+
+    Ok(())
+}
+
+/// Group the open todos by project, keeping the order in which projects first appear
+fn build_home_tab(
+    todos: &[Todo],
+    project_names: &HashMap<String, String>,
+    completed: Vec<String>,
+    today: NaiveDate,
+) -> HomeTab {
+    let mut projects: Vec<HomeProject> = Vec::new();
+    let mut project_ids: Vec<&str> = Vec::new();
+
+    for todo in todos.iter().filter(|todo| !todo.checked) {
+        let index = match project_ids.iter().position(|id| *id == todo.project_id) {
+            Some(index) => index,
+            None => {
+                project_ids.push(&todo.project_id);
+                projects.push(HomeProject {
+                    name: project_names
+                        .get(&todo.project_id)
+                        .cloned()
+                        .unwrap_or_else(|| "Other".to_string()),
+                    tasks: Vec::new(),
+                });
+                projects.len() - 1
+            }
+        };
+        projects[index].tasks.push(SlackTask {
+            id: todo.id.clone(),
+            content: todo.content.clone(),
+            priority: todo.priority,
+            labels: todo.labels.clone(),
+            overdue: is_overdue(todo, today),
+        });
+    }
+
+    HomeTab {
+        projects,
+        completed,
+        ..HomeTab::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(id: &str, project_id: &str, due: Option<&str>, checked: bool) -> Todo {
+        Todo {
+            project_id: project_id.to_string(),
+            due: due.map(test_support::due),
+            checked,
+            ..test_support::todo(id, &format!("Task {}", id))
+        }
+    }
+
+    #[test]
+    fn test_build_home_tab_groups_by_project() {
+        let todos = vec![
+            todo("1", "p-work", Some("2025-07-06"), false),
+            todo("2", "p-home", None, false),
+            todo("3", "p-work", Some("2025-07-07"), false),
+            todo("4", "p-work", None, true),
+            todo("5", "p-unknown", None, false),
+        ];
+        let project_names = HashMap::from([
+            ("p-work".to_string(), "Work".to_string()),
+            ("p-home".to_string(), "Home".to_string()),
+        ]);
+
+        let home = build_home_tab(
+            &todos,
+            &project_names,
+            vec!["Task 4".to_string()],
+            NaiveDate::from_ymd_opt(2025, 7, 7).unwrap(),
+        );
+
+        let names: Vec<&str> = home.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Work", "Home", "Other"]);
+
+        let work: Vec<(&str, bool)> = home.projects[0]
+            .tasks
+            .iter()
+            .map(|t| (t.id.as_str(), t.overdue))
+            .collect();
+        assert_eq!(work, [("1", true), ("3", false)]);
+        assert_eq!(home.completed, ["Task 4"]);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::destinations::{self, PostedMessages};
+use crate::state::DayState;
+use crate::{Config, current_date, sync_and_post};
+use slack::SlackClient;
+use std::error::Error;
+
+fn today(config: &Config) -> String {
+    current_date(config).format("%Y-%m-%d").to_string()
+}
+
+/// Delete every posted copy of today's message, keeping the ones that couldn't be deleted
+async fn delete_posted(
+    client: &SlackClient,
+    posted: &PostedMessages,
+    verb: &str,
+) -> PostedMessages {
+    let mut remaining = PostedMessages::new();
+    for (key, message) in posted {
+        let deleted = match destinations::channel_id(client, message).await {
+            Ok(channel) => client.delete_message(&channel, &message.ts).await,
+            Err(e) => Err(e),
+        };
+        match deleted {
+            Ok(()) => println!(
+                "🗑️  Deleted {} Slack message in {} ({})",
+                verb, key, message.ts
+            ),
+            Err(e) => {
+                eprintln!(
+                    "⚠️  Warning: Could not delete {} Slack message in {}: {}",
+                    verb, key, e
+                );
+                remaining.insert(key.clone(), message.clone());
+            }
+        }
+    }
+    remaining
+}
+
+/// Delete today's Slack messages and forget their IDs, so the next sync posts new ones
+pub async fn delete(config: &Config) -> Result<(), Box<dyn Error>> {
+    let date = today(config);
+    let mut state = DayState::load(config, &date);
+    if state.messages.is_empty() {
+        return Err("No Slack message has been posted for today".into());
+    }
+
+    let client = SlackClient::with_bot_token(config.slack_bot_token.clone())?;
+    state.messages = delete_posted(&client, &state.messages, "today's").await;
+    state.save(config, &date)?;
+
+    if !state.messages.is_empty() {
+        return Err(format!("{} message(s) could not be deleted", state.messages.len()).into());
+    }
+    Ok(())
+}
+
+/// Print a link to each of today's Slack messages
+pub async fn permalink(config: &Config) -> Result<(), Box<dyn Error>> {
+    let state = DayState::load(config, &today(config));
+    if state.messages.is_empty() {
+        return Err("No Slack message has been posted for today".into());
+    }
+
+    let client = SlackClient::with_bot_token(config.slack_bot_token.clone())?;
+    for (key, message) in &state.messages {
+        let channel = destinations::channel_id(&client, message).await?;
+        let link = client.get_permalink(&channel, &message.ts).await?;
+        println!("🔗 {}: {}", key, link);
+    }
+    Ok(())
+}
+
+/// Replace today's Slack messages with freshly posted ones
+///
+/// Messages that can't be deleted stay tracked and are updated instead.
+pub async fn repost(config: &Config) -> Result<(), Box<dyn Error>> {
+    let date = today(config);
+    let mut state = DayState::load(config, &date);
+
+    if !state.messages.is_empty() {
+        let client = SlackClient::with_bot_token(config.slack_bot_token.clone())?;
+        state.messages = delete_posted(&client, &state.messages, "previous").await;
+        state.save(config, &date)?;
+    }
+
+    sync_and_post(config).await
+}
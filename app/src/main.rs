@@ -1,12 +1,44 @@
-use chrono::Utc;
+mod attachment;
+mod capture;
+mod cli;
+mod destinations;
+mod digest;
+mod focus;
+mod home;
+mod lifecycle;
+mod render;
+mod rollover;
+mod schedule;
+mod server;
+mod slash;
+mod socket;
+mod standup;
+mod state;
+#[cfg(test)]
+mod test_support;
+mod watch;
+mod webhook;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use clap::Parser;
+use cli::{Cli, Command};
+use destinations::Destination;
+use render::{MarkdownOptions, MarkdownStyle, Templates};
+use schedule::Schedule;
 use serde::{Deserialize, Serialize};
-use slack::SlackClient;
-use std::collections::HashSet;
+use slack::{IconSet, SlackClient, SlackMessage, SlackTask};
+use standup::StandupOptions;
+use state::DayState;
 use std::env;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
 use todoist::{Todo, TodoistClient, TodoistError};
+use webhook::WebhookPolicy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Config {
@@ -15,6 +47,38 @@ struct Config {
     slack_channel: Option<String>,
     filter: Option<String>,
     todos_directory: Option<String>,
+    slack_signing_secret: Option<String>,
+    server_address: Option<String>,
+    slack_app_token: Option<String>,
+    todo_reaction: Option<String>,
+    slack_icons: Option<IconSet>,
+    slack_notification_text: Option<String>,
+    slack_webhook_url: Option<String>,
+    webhook_policy: Option<WebhookPolicy>,
+    webhook_summary_time: Option<String>,
+    slack_user_token: Option<String>,
+    focus_label: Option<String>,
+    focus_status_emoji: Option<String>,
+    slack_home_users: Option<Vec<String>>,
+    slack_attach_file: Option<bool>,
+    slack_allowed_mentions: Option<Vec<String>>,
+    slack_destinations: Option<Vec<Destination>>,
+    watch_interval: Option<u64>,
+    schedule: Option<Schedule>,
+    timezone: Option<String>,
+    carry_over_notes: Option<bool>,
+    markdown: Option<MarkdownOptions>,
+    templates: Option<Templates>,
+    standup: Option<StandupOptions>,
+    /// Day to act on instead of today, set with `--date`
+    #[serde(skip)]
+    date_override: Option<NaiveDate>,
+    /// How much detail to print, set with `-v`
+    #[serde(skip)]
+    verbosity: u8,
+    /// Timezone the days are counted in, resolved at startup by [`resolve_timezone`]
+    #[serde(skip)]
+    resolved_timezone: Option<Tz>,
 }
 
 impl Default for Config {
@@ -31,38 +95,53 @@ impl Default for Config {
             slack_channel: Some("#general".to_string()),
             filter: Some("(overdue | today) & #Work".to_string()),
             todos_directory: Some(default_todos_dir),
+            slack_signing_secret: None,
+            server_address: Some("127.0.0.1:3000".to_string()),
+            slack_app_token: None,
+            todo_reaction: Some(capture::DEFAULT_TODO_REACTION.to_string()),
+            slack_icons: None,
+            slack_notification_text: None,
+            slack_webhook_url: None,
+            webhook_policy: None,
+            webhook_summary_time: None,
+            slack_user_token: None,
+            focus_label: Some(focus::DEFAULT_FOCUS_LABEL.to_string()),
+            focus_status_emoji: Some(focus::DEFAULT_FOCUS_EMOJI.to_string()),
+            slack_home_users: None,
+            slack_attach_file: Some(false),
+            slack_allowed_mentions: None,
+            slack_destinations: None,
+            watch_interval: Some(watch::DEFAULT_WATCH_INTERVAL),
+            schedule: None,
+            timezone: None,
+            carry_over_notes: Some(false),
+            markdown: None,
+            templates: None,
+            standup: None,
+            date_override: None,
+            verbosity: 0,
+            resolved_timezone: None,
         }
     }
 }
 
-impl Config {
-    fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let home_dir = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let config_path = Path::new(&home_dir).join("slaist").join("config.toml");
+/// Where the config file lives unless `--config` says otherwise
+fn default_config_path() -> PathBuf {
+    let home_dir = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home_dir).join("slaist").join("config.toml")
+}
 
+impl Config {
+    fn load(config_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
         if !config_path.exists() {
-            // Create default config file
-            let default_config = Self::default();
-            let slaist_dir = Path::new(&home_dir).join("slaist");
-            fs::create_dir_all(&slaist_dir)?;
-
-            let toml_content = toml::to_string_pretty(&default_config)?;
-            fs::write(&config_path, toml_content)?;
-
-            println!(
-                "📝 Created default config file at: {}",
+            return Err(format!(
+                "No config file at {}, run `slaist init` to create one",
                 config_path.display()
-            );
-            println!("⚠️  Please edit the config file and add your API tokens:");
-            println!("   - todoist_api_token: Get from https://todoist.com/prefs/integrations");
-            println!("   - slack_bot_token: Get from your Slack app settings");
-
-            return Err(
-                "Config file created with default values. Please update with your tokens.".into(),
-            );
+            )
+            .into());
         }
 
-        let config_content = fs::read_to_string(&config_path)?;
+        let config_content = fs::read_to_string(config_path)?;
         let config: Config = toml::from_str(&config_content)?;
 
         // Validate required fields
@@ -72,9 +151,108 @@ impl Config {
         if config.slack_bot_token.is_empty() {
             return Err("slack_bot_token is required in config.toml".into());
         }
+        if let Some(timezone) = config.timezone.as_deref().filter(|tz| !tz.is_empty()) {
+            timezone.parse::<Tz>().map_err(|_| {
+                format!(
+                    "Unknown timezone {:?} in config.toml, expected a name like \"Europe/Amsterdam\"",
+                    timezone
+                )
+            })?;
+        }
 
         Ok(config)
     }
+
+    /// Write a config file with the default values
+    fn write_default(config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(directory) = config_path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let toml_content = toml::to_string_pretty(&Self::default())?;
+        fs::write(config_path, toml_content)?;
+        Ok(())
+    }
+}
+
+/// The system's timezone, UTC if it can't be determined
+fn system_timezone() -> Tz {
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// How long the Todoist timezone is used before it's looked up again
+const TIMEZONE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Pick the timezone days are counted in: `timezone` from the config, the
+/// Todoist user's timezone, or the system's
+///
+/// The Todoist timezone is cached in the state directory and looked up again
+/// once a day. With `online` unset Todoist isn't contacted at all, and any
+/// cached timezone is used however old it is.
+async fn resolve_timezone(config: &Config, online: bool) -> Tz {
+    if let Some(timezone) = config.timezone.as_deref().filter(|tz| !tz.is_empty()) {
+        if let Ok(timezone) = timezone.parse() {
+            return timezone;
+        }
+    }
+
+    let max_age = if online {
+        TIMEZONE_MAX_AGE
+    } else {
+        Duration::MAX
+    };
+    if let Some(timezone) = state::cached_timezone(config, max_age) {
+        return timezone;
+    }
+
+    if online {
+        let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+        match client.get_user().await {
+            Ok(user) => {
+                if let Some(timezone) = user
+                    .tz_info
+                    .and_then(|info| info.timezone.parse::<Tz>().ok())
+                {
+                    if let Err(e) = state::save_timezone(config, timezone) {
+                        eprintln!("⚠️  Warning: Could not cache the Todoist timezone: {}", e);
+                    }
+                    return timezone;
+                }
+                eprintln!("⚠️  Warning: Todoist didn't report a known timezone");
+            }
+            Err(e) => eprintln!("⚠️  Warning: Could not fetch the Todoist timezone: {}", e),
+        }
+        // An outdated Todoist timezone is still better than the system's
+        if let Some(timezone) = state::cached_timezone(config, Duration::MAX) {
+            return timezone;
+        }
+    }
+
+    let timezone = system_timezone();
+    eprintln!(
+        "⚠️  Warning: Counting days in the system timezone {}, set `timezone` in the config to choose one",
+        timezone
+    );
+    timezone
+}
+
+/// The timezone days are counted in
+fn timezone(config: &Config) -> Tz {
+    config.resolved_timezone.unwrap_or_else(system_timezone)
+}
+
+/// The current time in the configured timezone
+fn local_now(config: &Config) -> DateTime<Tz> {
+    Utc::now().with_timezone(&timezone(config))
+}
+
+/// The day to act on: `--date` if given, today otherwise
+fn current_date(config: &Config) -> NaiveDate {
+    config
+        .date_override
+        .unwrap_or_else(|| local_now(config).date_naive())
 }
 
 fn expand_tilde_path(path: &str) -> PathBuf {
@@ -82,8 +260,8 @@ fn expand_tilde_path(path: &str) -> PathBuf {
         let home_dir = env::var("HOME").unwrap_or_else(|_| ".".to_string());
         if path == "~" {
             PathBuf::from(home_dir)
-        } else if path.starts_with("~/") {
-            Path::new(&home_dir).join(&path[2..])
+        } else if let Some(rest) = path.strip_prefix("~/") {
+            Path::new(&home_dir).join(rest)
         } else {
             PathBuf::from(path)
         }
@@ -104,6 +282,16 @@ fn get_todos_directory(config: &Config) -> PathBuf {
 
 /// Parse existing markdown file to extract todo items
 fn parse_existing_markdown(content: &str) -> (Vec<(String, bool)>, Option<String>) {
+    let (todos, notes) = parse_todo_lines(content);
+    let todos = todos
+        .into_iter()
+        .map(|(content, done)| (render::strip_details(&content).to_string(), done))
+        .collect();
+    (todos, notes)
+}
+
+/// Like `parse_existing_markdown`, but keeps the details after each todo
+fn parse_todo_lines(content: &str) -> (Vec<(String, bool)>, Option<String>) {
     let mut todos = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
     let mut notes_section: Option<String> = None;
@@ -126,11 +314,11 @@ fn parse_existing_markdown(content: &str) -> (Vec<(String, bool)>, Option<String
         }
 
         // Handle standard markdown checkbox format
-        if trimmed.starts_with("- [ ]") {
-            let todo_content = trimmed[5..].trim().to_string();
-            todos.push((todo_content, false));
-        } else if trimmed.starts_with("- [x]") {
-            let mut todo_content = trimmed[5..].trim().to_string();
+        if let Some(rest) = trimmed.strip_prefix("- [ ]") {
+            let todo_content = rollover::strip_carried_flag(rest.trim());
+            todos.push((todo_content.to_string(), false));
+        } else if let Some(rest) = trimmed.strip_prefix("- [x]") {
+            let mut todo_content = rest.trim().to_string();
             // Remove the "*(marked as finished)*" suffix if present
             if todo_content.ends_with("*(marked as finished)*") {
                 todo_content = todo_content
@@ -141,11 +329,11 @@ fn parse_existing_markdown(content: &str) -> (Vec<(String, bool)>, Option<String
             todos.push((todo_content, true));
         }
         // Handle legacy emoji format for backward compatibility
-        else if trimmed.starts_with(":todo:") {
-            let todo_content = trimmed[6..].trim().to_string();
+        else if let Some(rest) = trimmed.strip_prefix(":todo:") {
+            let todo_content = rest.trim().to_string();
             todos.push((todo_content, false));
-        } else if trimmed.starts_with(":todo_done:") {
-            let mut todo_content = trimmed[11..].trim().to_string();
+        } else if let Some(rest) = trimmed.strip_prefix(":todo_done:") {
+            let mut todo_content = rest.trim().to_string();
             // Remove the "*(marked as finished)*" suffix if present
             if todo_content.ends_with("*(marked as finished)*") {
                 todo_content = todo_content
@@ -169,74 +357,68 @@ fn parse_existing_markdown(content: &str) -> (Vec<(String, bool)>, Option<String
 fn generate_markdown_content(
     current_todos: &[Todo],
     existing_todos: &[(String, bool)],
-    existing_message_id: Option<&str>,
     preserved_notes: Option<&str>,
+    style: &MarkdownStyle,
 ) -> String {
-    let mut content = String::new();
+    let view = render::daily_view(current_todos, existing_todos, preserved_notes, style);
 
-    // Preserve existing message ID if present
-    if let Some(message_id) = existing_message_id {
-        content.push_str(&format!("<!-- slack_message_id: {} -->\n", message_id));
+    if let Some(template) = &style.template {
+        match render::render_template(template, &view) {
+            Ok(content) => return content,
+            Err(e) => eprintln!(
+                "⚠️  Warning: Could not render the markdown template, using the default layout: {}",
+                e
+            ),
+        }
     }
 
-    // Create a set of current todo contents for fast lookup
-    let current_todo_contents: HashSet<String> = current_todos
-        .iter()
-        .map(|todo| todo.content.clone())
-        .collect();
+    let mut content = String::new();
+
+    // Open P1 todos are listed again at the top, without checkboxes so they're only parsed once
+    if style.options.high_priority && !view.high_priority.is_empty() {
+        content.push_str("## 🔥 High Priority\n\n");
+        for task in &view.high_priority {
+            content.push_str(&format!("- {}{}\n", task.content, task.details));
+        }
+        content.push('\n');
+    }
 
     // Active todos section
     content.push_str("## Active Todos\n\n");
 
-    let active_todos: Vec<_> = current_todos.iter().filter(|todo| !todo.checked).collect();
-    if active_todos.is_empty() {
+    if view.tasks.is_empty() {
         content.push_str("_No active todos found! 🎉_\n\n");
     } else {
-        for todo in active_todos {
-            content.push_str(&format!("- [ ] {}\n", todo.content));
-        }
+        content.push_str(&render::write_groups(&view.groups));
     }
 
     // Completed todos section
     content.push_str("\n## Completed Todos\n\n");
 
-    let completed_todos: Vec<_> = current_todos.iter().filter(|todo| todo.checked).collect();
-    let mut has_completed = false;
-
-    // Create a set to track completed todos we've already added
-    let mut added_completed: HashSet<String> = HashSet::new();
-
-    // Add currently completed todos
-    for todo in completed_todos {
-        content.push_str(&format!("- [x] {}\n", todo.content));
-        added_completed.insert(todo.content.clone());
-        has_completed = true;
-    }
-
-    // Add todos that were in markdown but no longer in current todos (mark as finished)
-    for (existing_content, was_completed) in existing_todos {
-        let in_current = current_todo_contents.contains(existing_content);
-        let already_added = added_completed.contains(existing_content);
-
-        if *was_completed && !already_added {
-            // Preserve previously completed todos (including those marked as finished)
-            content.push_str(&format!("- [x] {}\n", existing_content));
-            added_completed.insert(existing_content.clone());
-            has_completed = true;
-        } else if !*was_completed && !in_current && !already_added {
-            // Mark new missing todos as finished
-            content.push_str(&format!(
-                "- [x] {} *(marked as finished)*\n",
-                existing_content
-            ));
-            has_completed = true;
+    for todo in &view.completed {
+        if todo.marked_as_finished {
+            content.push_str(&format!("- [x] {} *(marked as finished)*\n", todo.content));
+        } else {
+            content.push_str(&format!("- [x] {}{}\n", todo.content, todo.details));
         }
     }
 
-    if !has_completed {
+    if view.completed.is_empty() {
         content.push_str("_No completed todos yet._\n\n");
     }
 
+    if style.options.stats {
+        let stats = &view.stats;
+        content.push_str("\n## Statistics\n\n");
+        content.push_str(&format!("- Open: {}\n", stats.open));
+        content.push_str(&format!("- Completed: {}\n", stats.completed));
+        if let Some(progress) = stats.progress {
+            content.push_str(&format!("- Progress: {}%\n", progress));
+        }
+        content.push_str(&format!("- High priority: {}\n", stats.high_priority));
+        content.push_str(&format!("- Overdue: {}\n", stats.overdue));
+    }
+
     // Add preserved notes section if it exists
     if let Some(notes) = preserved_notes {
         content.push_str("---\n");
@@ -250,47 +432,54 @@ fn generate_markdown_content(
     content
 }
 
-/// Extract Slack message ID from markdown content
-fn extract_slack_message_id(content: &str) -> Option<String> {
-    for line in content.lines() {
-        if line.starts_with("<!-- slack_message_id: ") && line.ends_with(" -->") {
-            let id = line
-                .trim_start_matches("<!-- slack_message_id: ")
-                .trim_end_matches(" -->")
-                .to_string();
-            return Some(id);
-        }
+/// Build today's Slack message from its text, with a checkbox for each open task
+fn build_slack_message(config: &Config, date: &str, text: String, todos: &[Todo]) -> SlackMessage {
+    let today = current_date(config);
+    let tasks = todos
+        .iter()
+        .filter(|todo| !todo.checked)
+        .map(|todo| SlackTask {
+            id: todo.id.clone(),
+            content: todo.content.clone(),
+            priority: todo.priority,
+            labels: todo.labels.clone(),
+            overdue: is_overdue(todo, today),
+        })
+        .collect();
+    let message = SlackMessage::new(text).with_tasks(tasks);
+
+    match &config.slack_notification_text {
+        Some(template) => message.with_fallback_text(notification_text(template, date, todos)),
+        None => message,
     }
-    None
 }
 
-/// Add or update Slack message ID in markdown content
-fn add_slack_message_id(content: &str, message_id: &str) -> String {
-    let metadata_line = format!("<!-- slack_message_id: {} -->", message_id);
-
-    // Check if there's already a message ID
-    let mut lines: Vec<&str> = content.lines().collect();
-
-    // Find and replace existing message ID line
-    for (i, line) in lines.iter().enumerate() {
-        if line.starts_with("<!-- slack_message_id: ") && line.ends_with(" -->") {
-            lines[i] = &metadata_line;
-            return lines.join("\n");
-        }
-    }
+/// Fill in `{date}`, `{open}` and `{done}` in the configured notification text
+fn notification_text(template: &str, date: &str, todos: &[Todo]) -> String {
+    let done = todos.iter().filter(|todo| todo.checked).count();
+    template
+        .replace("{date}", date)
+        .replace("{open}", &(todos.len() - done).to_string())
+        .replace("{done}", &done.to_string())
+}
 
-    // If no existing message ID found, add it at the beginning
-    format!("{}\n{}", metadata_line, content)
+/// Whether a todo's due date lies before `today`
+fn is_overdue(todo: &Todo, today: NaiveDate) -> bool {
+    todo.due
+        .as_ref()
+        .and_then(|due| due.date.get(..10))
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .is_some_and(|date| date < today)
 }
 
-/// Filter out Slack message ID metadata from markdown content
+/// Filter out Slack metadata comments from markdown content
 fn filter_slack_metadata(content: &str) -> String {
     let mut result = Vec::new();
     let mut in_notes_section = false;
 
     for line in content.lines() {
-        // Skip Slack message ID comments
-        if line.starts_with("<!-- slack_message_id: ") && line.ends_with(" -->") {
+        // Skip Slack message ID and webhook comments
+        if line.starts_with("<!-- slack_") && line.ends_with(" -->") {
             continue;
         }
 
@@ -302,7 +491,7 @@ fn filter_slack_metadata(content: &str) -> String {
 
         // Only include lines that are not in the notes section
         if !in_notes_section {
-            result.push(line);
+            result.push(render::strip_line_details(line));
         }
     }
 
@@ -323,27 +512,139 @@ fn validate_message_id(message_id: &str) -> bool {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), TodoistError> {
-    println!("🚀 Todoist Client - Single Run");
-    println!("=======================================");
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let config_path = cli.config.clone().unwrap_or_else(default_config_path);
+    let command = cli.command.unwrap_or(Command::Run);
+
+    if let Command::Init { force } = command {
+        return cli::report(
+            cli::init(&config_path, force),
+            "Could not create the config file",
+        );
+    }
 
     // Load configuration from TOML file
-    let config = match Config::load() {
+    let mut config = match Config::load(&config_path) {
         Ok(config) => config,
         Err(e) => {
-            println!("❌ Failed to load configuration: {}", e);
-            return Ok(());
+            eprintln!("❌ Failed to load configuration: {}", e);
+            return ExitCode::FAILURE;
         }
     };
+    config.date_override = cli.date;
+    config.verbosity = cli.verbose;
+    config.resolved_timezone = Some(resolve_timezone(&config, !command.is_offline()).await);
+    if config.verbosity > 0 {
+        println!("⚙️  Using config file {}", config_path.display());
+        println!("🌍 Counting days in {}", timezone(&config));
+    }
+
+    match command {
+        Command::Init { .. } => unreachable!("handled before loading the config"),
+        Command::Run => {
+            println!("🚀 Todoist Client - Single Run");
+            println!("=======================================");
+
+            let result = sync_and_post(&config).await;
 
-    // Create Todoist client
+            println!("\n{:-<60}", "");
+            if result.is_ok() {
+                println!("✅ Complete!");
+            }
+            cli::report(result, "Run failed")
+        }
+        Command::Watch { interval } => {
+            let interval = interval
+                .or(config.watch_interval)
+                .unwrap_or(watch::DEFAULT_WATCH_INTERVAL);
+            cli::report(
+                watch::run(&config, Duration::from_secs(interval)).await,
+                "Watch mode failed",
+            )
+        }
+        Command::Sync => match try_sync_todos(&config, &MarkdownStyle::load(&config).await).await {
+            Some(_) => ExitCode::SUCCESS,
+            None => ExitCode::FAILURE,
+        },
+        Command::Post => {
+            let result = match fetch_todos(&config).await {
+                Some(todos) => {
+                    post_slack(&config, &todos, &MarkdownStyle::load(&config).await).await
+                }
+                None => Err("Could not fetch todos from Todoist".into()),
+            };
+            cli::report(result, "Error posting to Slack")
+        }
+        Command::Status => cli::report(cli::status(&config), "Could not show the status"),
+        Command::Doctor => cli::report(
+            cli::doctor(&config, &config_path).await,
+            "Setup needs attention",
+        ),
+        Command::Show { date } => {
+            config.date_override = date.or(config.date_override);
+            cli::report(cli::show(&config), "Could not show the todo file")
+        }
+        Command::Serve => cli::report(server::serve(config).await, "Server error"),
+        Command::Socket => cli::report(socket::run(config).await, "Socket Mode error"),
+        Command::Delete => cli::report(
+            lifecycle::delete(&config).await,
+            "Could not delete today's Slack message",
+        ),
+        Command::Permalink => cli::report(
+            lifecycle::permalink(&config).await,
+            "Could not get a link to today's Slack message",
+        ),
+        Command::Repost => cli::report(
+            lifecycle::repost(&config).await,
+            "Could not repost today's Slack message",
+        ),
+        Command::Digest { period, post } => cli::report(
+            digest::run(&config, period, post).await,
+            "Could not write the digest",
+        ),
+        Command::Standup { thread } => cli::report(
+            standup::run(&config, thread).await,
+            "Could not post the standup",
+        ),
+    }
+}
+
+/// Fetch the current todos from Todoist, printing hints if that fails
+async fn fetch_todos(config: &Config) -> Option<Vec<Todo>> {
     let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
 
+    match client.get_all_todos().await {
+        Ok(todos) => {
+            println!("📋 Fetched {} todos total", todos.len());
+            Some(todos)
+        }
+        Err(e) => {
+            eprintln!("❌ Error fetching todos: {}", e);
+            match e {
+                TodoistError::ApiError { status: 401, .. } => {
+                    eprintln!("🔑 Check your API token - it might be invalid or expired");
+                }
+                TodoistError::RequestFailed(_) => {
+                    eprintln!("🌐 Network error - check your internet connection");
+                }
+                _ => {}
+            }
+            None
+        }
+    }
+}
+
+/// Fetch the current todos and reconcile them into today's markdown file
+///
+/// Returns the todos fetched from Todoist, or `None` if the request failed, in
+/// which case the file is left alone.
+async fn try_sync_todos(config: &Config, style: &MarkdownStyle) -> Option<Vec<Todo>> {
     println!("📱 Fetching todos...");
     println!();
 
     // Create the todos directory if it doesn't exist
-    let slaist_dir = get_todos_directory(&config);
+    let slaist_dir = get_todos_directory(config);
     if let Err(e) = fs::create_dir_all(&slaist_dir) {
         eprintln!(
             "⚠️  Warning: Could not create directory {}: {}",
@@ -353,56 +654,53 @@ async fn main() -> Result<(), TodoistError> {
     }
 
     let now = Utc::now();
-    let timestamp = now.format("%Y-%m-%d %H:%M:%S UTC");
-    let date_str = now.format("%Y-%m-%d");
+    let timestamp = now
+        .with_timezone(&timezone(config))
+        .format("%Y-%m-%d %H:%M:%S %Z");
+    let date_str = current_date(config).format("%Y-%m-%d").to_string();
     let filename = format!("{}.md", date_str);
     let file_path = slaist_dir.join(&filename);
 
     println!("🔄 Processing - {}", timestamp);
     println!("{:-<60}", "");
 
+    // Load today's state first, it moves metadata left in the markdown by earlier versions
+    let mut state = DayState::load(config, &date_str);
+
     // Read existing markdown file if it exists
-    let (existing_todos, existing_message_id, preserved_notes) = if file_path.exists() {
-        match fs::read_to_string(&file_path) {
-            Ok(content) => {
-                let (todos, notes) = parse_existing_markdown(&content);
-                let message_id = extract_slack_message_id(&content);
-                (todos, message_id, notes)
-            }
-            Err(_) => (Vec::new(), None, None),
-        }
+    let existing_content = if file_path.exists() {
+        fs::read_to_string(&file_path).unwrap_or_default()
     } else {
-        (Vec::new(), None, None)
+        String::new()
     };
+    let (existing_todos, mut preserved_notes) = parse_todo_lines(&existing_content);
 
     // Fetch all current todos (active and completed from recent days)
-    let all_current_todos = match client.get_all_todos().await {
-        Ok(todos) => {
-            println!("📋 Fetched {} todos total", todos.len());
-            todos
-        }
-        Err(e) => {
-            eprintln!("❌ Error fetching todos: {}", e);
-            match e {
-                TodoistError::ApiError { status, .. } if status == 401 => {
-                    eprintln!("🔑 Check your API token - it might be invalid or expired");
-                }
-                TodoistError::RequestFailed(_) => {
-                    eprintln!("🌐 Network error - check your internet connection");
-                }
-                _ => {}
-            }
-            Vec::new()
-        }
+    // Rewriting the file without todos would mark every open todo as finished
+    let Some(all_current_todos) = fetch_todos(config).await else {
+        println!("⏭️  Keeping {} as it is", file_path.display());
+        return None;
     };
+    state.last_sync = Some(now);
 
-    println!("existing_todos: {:?}", existing_todos);
+    // The first sync of a day finalizes the previous one and takes over what's left of it
+    if !file_path.exists() {
+        let carried =
+            rollover::roll_over(config, current_date(config), &all_current_todos, style).await;
+        state.carried = carried.todos;
+        preserved_notes = carried.notes;
+    }
+
+    if config.verbosity > 0 {
+        println!("existing_todos: {:?}", existing_todos);
+    }
 
     // Check which todos are missing for summary
     let missing_count = existing_todos
         .iter()
         .filter(|(content, was_completed)| {
-            !was_completed && !all_current_todos.iter().any(|t| t.content == *content)
+            let content = render::strip_details(content);
+            !was_completed && !all_current_todos.iter().any(|t| t.content == content)
         })
         .count();
 
@@ -410,16 +708,20 @@ async fn main() -> Result<(), TodoistError> {
     let preserved_count = existing_todos
         .iter()
         .filter(|(content, was_completed)| {
-            *was_completed && !all_current_todos.iter().any(|t| t.content == *content)
+            let content = render::strip_details(content);
+            *was_completed && !all_current_todos.iter().any(|t| t.content == content)
         })
         .count();
 
     // Generate markdown content with comparison logic
-    let markdown_content = generate_markdown_content(
-        &all_current_todos,
-        &existing_todos,
-        existing_message_id.as_deref(),
-        preserved_notes.as_deref(),
+    let markdown_content = rollover::flag_carried(
+        &generate_markdown_content(
+            &all_current_todos,
+            &existing_todos,
+            preserved_notes.as_deref(),
+            style,
+        ),
+        &state.carried,
     );
 
     // Display summary
@@ -457,21 +759,114 @@ async fn main() -> Result<(), TodoistError> {
         }
     }
 
-    let _ = post_slack(&config).await;
+    state.todos = all_current_todos.clone();
+    // Finished todos stay in the markdown, so keep their IDs too
+    state.task_ids.extend(
+        all_current_todos
+            .iter()
+            .map(|todo| (todo.content.clone(), todo.id.clone())),
+    );
+    // The digest counts completions per project as it was when they were completed
+    let newly_completed: Vec<&Todo> = all_current_todos
+        .iter()
+        .filter(|todo| todo.checked && !state.completed_projects.contains_key(&todo.id))
+        .collect();
+    if !newly_completed.is_empty() {
+        // The style only has the names when they're shown
+        let fetched;
+        let projects = if style.options.project {
+            &style.projects
+        } else {
+            fetched = render::project_names(config).await;
+            &fetched
+        };
+        for todo in newly_completed {
+            if let Some(project) = projects.get(&todo.project_id) {
+                state
+                    .completed_projects
+                    .insert(todo.id.clone(), project.clone());
+            }
+        }
+    }
+    if let Err(e) = state.save(config, &date_str) {
+        eprintln!("⚠️  Warning: Could not save today's state: {}", e);
+    }
+
+    Some(all_current_todos)
+}
 
-    println!("\n{:-<60}", "");
-    println!("✅ Complete!");
+/// Sync today's markdown file, post or update the Slack message from it and refresh
+/// the status and Home tab
+///
+/// The message is posted from the file and the last sync's todos even if Todoist
+/// can't be reached, but the failed sync is still returned as an error.
+async fn sync_and_post(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let style = MarkdownStyle::load(config).await;
+    let synced = try_sync_todos(config, &style).await;
+    let fetched = synced.is_some();
+    let todos = todos_to_post(config, synced);
+    // Boxed errors aren't Send, so keep only the message while the rest is updated
+    let posted = post_slack(config, &todos, &style)
+        .await
+        .map_err(|e| e.to_string());
+    // The last sync's todos could be stale, so the status is left as it is
+    if fetched {
+        if let Err(e) = focus::sync_status(config, &todos).await {
+            eprintln!("❌ Error updating Slack status: {}", e);
+        }
+    }
+    home::refresh(config).await;
 
-    Ok(())
+    if !fetched {
+        return Err("Could not fetch todos from Todoist".into());
+    }
+    Ok(posted?)
+}
+
+/// The todos to post: the ones just synced, or those of today's last sync if
+/// Todoist couldn't be reached, so a failed request doesn't empty the message
+fn todos_to_post(config: &Config, synced: Option<Vec<Todo>>) -> Vec<Todo> {
+    synced.unwrap_or_else(|| {
+        let date_str = current_date(config).format("%Y-%m-%d").to_string();
+        DayState::load(config, &date_str).todos
+    })
+}
+
+/// Run the work for a Slack request without waiting for it to finish
+///
+/// Slack expects an answer within 3 seconds, so anything that goes through Todoist
+/// or Slack is done in the background after answering.
+fn in_background(work: impl Future<Output = ()> + Send + 'static) {
+    tokio::spawn(work);
 }
 
-async fn post_slack(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+/// Sync and post without waiting for it to finish, printing the error if it fails
+fn spawn_sync_and_post(config: Arc<Config>) {
+    in_background(async move {
+        if let Err(e) = sync_and_post(&config).await {
+            eprintln!("❌ Error syncing and posting: {}", e);
+        }
+    });
+}
+
+async fn post_slack(
+    config: &Config,
+    todos: &[Todo],
+    style: &MarkdownStyle,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("📤 Slack Post - Sending Today's Todos");
     println!("=====================================");
 
+    if let Some(webhook_url) = config
+        .slack_webhook_url
+        .as_deref()
+        .filter(|url| !url.is_empty())
+    {
+        return webhook::post(config, todos, webhook_url, style).await;
+    }
+
     // Get today's date
-    let now = Utc::now();
-    let date_str = now.format("%Y-%m-%d");
+    let date_str = current_date(config).format("%Y-%m-%d").to_string();
 
     // Construct the path to today's markdown file
     let slaist_dir = get_todos_directory(config);
@@ -481,20 +876,14 @@ async fn post_slack(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
 
     // Check if the file exists
     if !file_path.exists() {
-        eprintln!("❌ Error: Todo file for today ({}) not found!", date_str);
         eprintln!("   Expected location: {}", file_path.display());
         eprintln!("   Run the main slaist application first to generate the file.");
-        return Ok(());
+        return Err(format!("Todo file for today ({}) not found", date_str).into());
     }
 
     // Read the markdown content
-    let markdown_content = match fs::read_to_string(&file_path) {
-        Ok(content) => content,
-        Err(e) => {
-            eprintln!("❌ Error reading file {}: {}", file_path.display(), e);
-            return Ok(());
-        }
-    };
+    let markdown_content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Could not read {}: {}", file_path.display(), e))?;
 
     if markdown_content.trim().is_empty() {
         eprintln!("⚠️  Warning: Todo file is empty!");
@@ -506,25 +895,19 @@ async fn post_slack(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
         markdown_content.len()
     );
 
-    // Extract existing Slack message ID if present
-    let existing_message_id = extract_slack_message_id(&markdown_content);
-
-    // Validate message ID if present
-    if let Some(ref msg_id) = existing_message_id {
-        if !validate_message_id(msg_id) {
-            eprintln!("⚠️  Warning: Invalid message ID format found: {}", msg_id);
-            eprintln!("   Expected format: 1234567890.123456");
-            eprintln!("   Will attempt to post as new message instead");
-        } else {
-            println!("📋 Found existing message ID: {}", msg_id);
-        }
+    // Look up the messages already posted today, per destination
+    let destinations = destinations::configured(config);
+    let mut state = DayState::load(config, &date_str);
+    for (key, message) in &state.messages {
+        println!("📋 Found existing message in {}: {}", key, message.ts);
     }
 
     // Create Slack client
     let slack_client = match SlackClient::with_bot_token(config.slack_bot_token.clone()) {
-        Ok(client) => client,
+        Ok(client) => client
+            .with_icons(config.slack_icons.clone().unwrap_or_default())
+            .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default()),
         Err(e) => {
-            eprintln!("❌ Error creating Slack client: {}", e);
             eprintln!("   Please check your config file at ~/slaist/config.toml");
             eprintln!("   Make sure slack_bot_token is set correctly.");
             eprintln!("   ");
@@ -540,156 +923,52 @@ async fn post_slack(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
             eprintln!(
                 "   slack_channel = \"#your-channel-name\"  # optional, defaults to #general"
             );
-            return Ok(());
+            return Err(format!("Could not create Slack client: {}", e).into());
         }
     };
 
-    // Filter out metadata from markdown content before sending to Slack
-    let filtered_markdown = filter_slack_metadata(&markdown_content);
-
-    // Prepare the message
-    let message = format!("📅 *Daily Todos - {}*\n\n{}", date_str, filtered_markdown);
-
-    // Get channel from config or use default
-    let channel = config.slack_channel.as_deref().unwrap_or("#general");
-
-    println!("📢 Posting to channel: {}", channel);
-
-    // Post or update message to Slack
-    match existing_message_id {
-        Some(message_id) if validate_message_id(&message_id) => {
-            // Update existing message
-            println!("🔄 Updating existing message...");
-            match slack_client
-                .update_message(&message, &channel, &message_id)
+    let text = render::slack_text(config, style, &date_str, &markdown_content, todos, false);
+    let attach_file = config.slack_attach_file.unwrap_or(false);
+    let mut message = build_slack_message(config, &date_str, text, todos);
+    if attach_file {
+        message = attachment::summarize_message(message, &date_str, todos);
+    }
+    let failed =
+        destinations::deliver_all(&slack_client, &destinations, &message, &mut state.messages)
+            .await;
+    println!("   Date: {}", date_str);
+    println!("   Content length: {} characters", message.text.len());
+
+    // The file is attached under the first destination's message
+    let first_posted = destinations
+        .first()
+        .and_then(|destination| state.messages.get(&destination.key()))
+        .cloned();
+    if let (true, Some(posted)) = (attach_file, first_posted) {
+        if let Err(e) =
+            attachment::upload_daily_file(&slack_client, &file_path, &posted, &date_str, &mut state)
                 .await
-            {
-                Ok(_) => {
-                    println!("✅ Successfully updated today's todos on Slack!");
-                    println!("   Date: {}", date_str);
-                    println!("   Channel: {}", channel);
-                    println!("   Message ID: {}", message_id);
-                    println!("   Content length: {} characters", filtered_markdown.len());
-                }
-                Err(e) => {
-                    eprintln!("❌ Error updating Slack message: {}", e);
-                    eprintln!("   Message ID: {}", message_id);
-                    eprintln!("   Channel: {}", channel);
-
-                    // Provide specific error guidance
-                    match e {
-                        slack::SlackError::ApiError(ref msg) => {
-                            if msg.contains("cant_update_message") {
-                                eprintln!(
-                                    "   → This usually means the bot doesn't have permission to update this message"
-                                );
-                                eprintln!("   → Or the message was posted by a different bot/user");
-                            } else if msg.contains("message_not_found") {
-                                eprintln!("   → The message with this ID no longer exists");
-                            }
-                        }
-                        _ => {}
-                    }
-
-                    // Try to post as new message if update fails
-                    println!("🔄 Attempting to post as new message...");
-                    match slack_client.post_message(&message, &channel).await {
-                        Ok(new_message_id) => {
-                            println!("✅ Successfully posted new message to Slack!");
-                            println!("   New Message ID: {}", new_message_id);
-                            // Update the markdown file with new message ID
-                            let updated_content =
-                                add_slack_message_id(&markdown_content, &new_message_id);
-                            if let Err(e) = fs::write(&file_path, updated_content) {
-                                eprintln!(
-                                    "⚠️  Warning: Could not update file with new message ID: {}",
-                                    e
-                                );
-                            } else {
-                                println!("📝 Updated markdown file with new message ID");
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("❌ Error posting new message to Slack: {}", e);
-                        }
-                    }
-                }
-            }
-        }
-        Some(invalid_id) => {
-            eprintln!(
-                "⚠️  Skipping update due to invalid message ID: {}",
-                invalid_id
-            );
-            println!("🚀 Sending new message to Slack...");
-            match slack_client.post_message(&message, &channel).await {
-                Ok(message_id) => {
-                    println!("✅ Successfully posted today's todos to Slack!");
-                    println!("   Date: {}", date_str);
-                    println!("   Channel: {}", channel);
-                    println!("   Message ID: {}", message_id);
-                    println!("   Content length: {} characters", filtered_markdown.len());
-
-                    // Update the markdown file with the new valid message ID
-                    let updated_content = add_slack_message_id(&markdown_content, &message_id);
-                    if let Err(e) = fs::write(&file_path, updated_content) {
-                        eprintln!("⚠️  Warning: Could not update file with message ID: {}", e);
-                    } else {
-                        println!("📝 Updated markdown file with valid message ID");
-                    }
-                }
-                Err(e) => {
-                    eprintln!("❌ Error posting to Slack: {}", e);
-                }
-            }
-        }
-        None => {
-            // Post new message
-            println!("🚀 Sending new message to Slack...");
-            match slack_client.post_message(&message, &channel).await {
-                Ok(message_id) => {
-                    println!("✅ Successfully posted today's todos to Slack!");
-                    println!("   Date: {}", date_str);
-                    println!("   Channel: {}", channel);
-                    println!("   Message ID: {}", message_id);
-                    println!("   Content length: {} characters", filtered_markdown.len());
-
-                    // Update the markdown file with the message ID
-                    let updated_content = add_slack_message_id(&markdown_content, &message_id);
-                    if let Err(e) = fs::write(&file_path, updated_content) {
-                        eprintln!("⚠️  Warning: Could not update file with message ID: {}", e);
-                    } else {
-                        println!("📝 Updated markdown file with message ID for future updates");
-                    }
-                }
-                Err(e) => {
-                    eprintln!("❌ Error posting to Slack: {}", e);
-                    match e {
-                        slack::SlackError::HttpError(_) => {
-                            eprintln!("   This might be a network connectivity issue.");
-                        }
-                        slack::SlackError::ApiError(ref msg) => {
-                            eprintln!("   Slack API error: {}", msg);
-                            if msg.contains("invalid_auth") || msg.contains("not_authed") {
-                                eprintln!("   Check your Slack bot token.");
-                            }
-                        }
-                        slack::SlackError::ConfigError(_) => {
-                            eprintln!("   Check your slack_bot_token in ~/slaist/config.toml");
-                        }
-                        _ => {}
-                    }
-                }
-            }
+        {
+            eprintln!("❌ Error attaching today's file to Slack: {}", e);
         }
     }
 
+    if let Err(e) = state.save(config, &date_str) {
+        eprintln!("⚠️  Warning: Could not save message IDs: {}", e);
+    } else {
+        println!("📝 Saved message IDs for future updates");
+    }
+
+    if !failed.is_empty() {
+        return Err(format!("Could not post to {}", failed.join(", ")).into());
+    }
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support;
     use todoist::Todo;
 
     #[test]
@@ -796,7 +1075,12 @@ _No completed todos yet._
             ("Already completed task".to_string(), true),
         ];
 
-        let markdown = generate_markdown_content(&current_todos, &existing_todos, None, None);
+        let markdown = generate_markdown_content(
+            &current_todos,
+            &existing_todos,
+            None,
+            &MarkdownStyle::plain(),
+        );
 
         assert!(markdown.contains("## Active Todos"));
         assert!(markdown.contains("- [ ] Active task"));
@@ -810,7 +1094,12 @@ _No completed todos yet._
     fn test_generate_markdown_content_no_todos() {
         let current_todos = vec![];
         let existing_todos = vec![];
-        let markdown = generate_markdown_content(&current_todos, &existing_todos, None, None);
+        let markdown = generate_markdown_content(
+            &current_todos,
+            &existing_todos,
+            None,
+            &MarkdownStyle::plain(),
+        );
 
         assert!(markdown.contains("_No active todos found! 🎉_"));
         assert!(markdown.contains("_No completed todos yet._"));
@@ -825,7 +1114,12 @@ _No completed todos yet._
             ("Already completed".to_string(), true),
         ];
 
-        let markdown = generate_markdown_content(&current_todos, &existing_todos, None, None);
+        let markdown = generate_markdown_content(
+            &current_todos,
+            &existing_todos,
+            None,
+            &MarkdownStyle::plain(),
+        );
 
         assert!(markdown.contains("- [x] Missing task 1 *(marked as finished)*"));
         assert!(markdown.contains("- [x] Missing task 2 *(marked as finished)*"));
@@ -866,7 +1160,12 @@ _No completed todos yet._
             ("Task that disappeared".to_string(), false),   // No longer in API
         ];
 
-        let markdown = generate_markdown_content(&current_todos, &existing_todos, None, None);
+        let markdown = generate_markdown_content(
+            &current_todos,
+            &existing_todos,
+            None,
+            &MarkdownStyle::plain(),
+        );
 
         // Should show the task as completed (from API)
         assert!(markdown.contains("- [x] Task that was completed"));
@@ -911,7 +1210,12 @@ _No completed todos yet._
             ("Task that just disappeared".to_string(), false), // New missing task
         ];
 
-        let markdown = generate_markdown_content(&current_todos, &existing_todos, None, None);
+        let markdown = generate_markdown_content(
+            &current_todos,
+            &existing_todos,
+            None,
+            &MarkdownStyle::plain(),
+        );
 
         // Should preserve previously finished todos
         assert!(markdown.contains("- [x] Old task marked as finished"));
@@ -982,7 +1286,8 @@ _No completed todos yet._
         ];
 
         // First iteration: generate markdown from initial todos
-        let first_markdown = generate_markdown_content(&initial_todos, &[], None, None);
+        let first_markdown =
+            generate_markdown_content(&initial_todos, &[], None, &MarkdownStyle::plain());
         assert!(first_markdown.contains("- [ ] Task A"));
         assert!(first_markdown.contains("- [ ] Task B"));
 
@@ -991,7 +1296,8 @@ _No completed todos yet._
 
         // Second iteration: Task B disappears (maybe completed outside the filter)
         let second_todos = vec![initial_todos[0].clone()]; // Only Task A remains
-        let second_markdown = generate_markdown_content(&second_todos, &first_parsed, None, None);
+        let second_markdown =
+            generate_markdown_content(&second_todos, &first_parsed, None, &MarkdownStyle::plain());
 
         // Task B should be marked as finished
         assert!(second_markdown.contains("- [ ] Task A"));
@@ -1027,7 +1333,8 @@ _No completed todos yet._
             responsible_uid: None,
         }];
 
-        let third_markdown = generate_markdown_content(&third_todos, &second_parsed, None, None);
+        let third_markdown =
+            generate_markdown_content(&third_todos, &second_parsed, None, &MarkdownStyle::plain());
 
         // Should preserve Task B as finished from previous iteration (without suffix)
         // Should mark Task A as newly finished (with suffix)
@@ -1043,47 +1350,6 @@ _No completed todos yet._
         assert!(completed_section.contains("Task B"));
     }
 
-    #[test]
-    fn test_extract_slack_message_id() {
-        let content_with_id = r#"<!-- slack_message_id: 1234567890.123456 -->
-## Active Todos
-
-- [ ] Test task
-"#;
-        let extracted = extract_slack_message_id(content_with_id);
-        assert_eq!(extracted, Some("1234567890.123456".to_string()));
-
-        let content_without_id = r#"## Active Todos
-
-- [ ] Test task
-"#;
-        let extracted = extract_slack_message_id(content_without_id);
-        assert_eq!(extracted, None);
-    }
-
-    #[test]
-    fn test_add_slack_message_id() {
-        let content = r#"## Active Todos
-
-- [ ] Test task
-"#;
-        let result = add_slack_message_id(content, "1234567890.123456");
-        assert!(result.starts_with("<!-- slack_message_id: 1234567890.123456 -->"));
-        assert!(result.contains("## Active Todos"));
-    }
-
-    #[test]
-    fn test_update_slack_message_id() {
-        let content = r#"<!-- slack_message_id: old_id -->
-## Active Todos
-
-- [ ] Test task
-"#;
-        let result = add_slack_message_id(content, "new_id");
-        assert!(result.contains("<!-- slack_message_id: new_id -->"));
-        assert!(!result.contains("old_id"));
-    }
-
     #[test]
     fn test_filter_slack_metadata() {
         let content = r#"<!-- slack_message_id: 1234567890.123456 -->
@@ -1169,15 +1435,8 @@ More private content here.
     }
 
     #[test]
-    fn test_generate_markdown_content_with_message_id() {
-        let markdown = generate_markdown_content(&[], &[], Some("1234567890.123456"), None);
-        assert!(markdown.starts_with("<!-- slack_message_id: 1234567890.123456 -->"));
-        assert!(markdown.contains("## Active Todos"));
-    }
-
-    #[test]
-    fn test_message_id_preservation_during_regeneration() {
-        // Create initial markdown with a message ID
+    fn test_regeneration_drops_legacy_metadata() {
+        // Create initial markdown with a message ID written by an earlier version
         let initial_content = r#"<!-- slack_message_id: 1234567890.123456 -->
 ## Active Todos
 
@@ -1191,7 +1450,6 @@ More private content here.
 
         // Parse the existing content
         let (existing_todos, _) = parse_existing_markdown(initial_content);
-        let message_id = extract_slack_message_id(initial_content);
 
         // Simulate new todos from API
         let new_todos = vec![
@@ -1249,10 +1507,10 @@ More private content here.
 
         // Generate new markdown content
         let regenerated_content =
-            generate_markdown_content(&new_todos, &existing_todos, message_id.as_deref(), None);
+            generate_markdown_content(&new_todos, &existing_todos, None, &MarkdownStyle::plain());
 
-        // Verify the message ID is preserved
-        assert!(regenerated_content.starts_with("<!-- slack_message_id: 1234567890.123456 -->"));
+        // Metadata lives in the state file, so the markdown only holds todos
+        assert!(regenerated_content.starts_with("## Active Todos"));
 
         // Verify the content structure is correct
         assert!(regenerated_content.contains("## Active Todos"));
@@ -1261,10 +1519,7 @@ More private content here.
         assert!(regenerated_content.contains("## Completed Todos"));
         assert!(regenerated_content.contains("- [x] Task B *(marked as finished)*")); // Task B disappeared
         assert!(regenerated_content.contains("- [x] Task C")); // Task C was already completed
-
-        // Verify we can extract the message ID from the regenerated content
-        let extracted_id = extract_slack_message_id(&regenerated_content);
-        assert_eq!(extracted_id, Some("1234567890.123456".to_string()));
+        assert!(!regenerated_content.contains("slack_message_id"));
     }
 
     #[test]
@@ -1304,6 +1559,7 @@ More private content here.
             slack_channel: None,
             filter: None,
             todos_directory: Some("~/custom/todos".to_string()),
+            ..Config::default()
         };
 
         let result = get_todos_directory(&config);
@@ -1320,6 +1576,7 @@ More private content here.
             slack_channel: None,
             filter: None,
             todos_directory: None,
+            ..Config::default()
         };
 
         let result = get_todos_directory(&config);
@@ -1328,6 +1585,70 @@ More private content here.
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_current_date_in_timezone() {
+        let in_zone = |name: &str| Config {
+            resolved_timezone: Some(name.parse().unwrap()),
+            ..Config::default()
+        };
+
+        // UTC+14 and UTC-12 are 26 hours apart, so never on the same day
+        let ahead = current_date(&in_zone("Pacific/Kiritimati"));
+        let behind = current_date(&in_zone("Etc/GMT+12"));
+        assert!(ahead > behind);
+
+        let overridden = Config {
+            date_override: NaiveDate::from_ymd_opt(2025, 7, 7),
+            ..in_zone("Pacific/Kiritimati")
+        };
+        assert_eq!(
+            current_date(&overridden),
+            NaiveDate::from_ymd_opt(2025, 7, 7).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_todos_to_post_falls_back_to_the_last_sync() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = Config {
+            todos_directory: Some(directory.path().to_string_lossy().to_string()),
+            ..Config::default()
+        };
+        let date_str = current_date(&config).format("%Y-%m-%d").to_string();
+        let state = DayState {
+            todos: vec![test_support::todo("1", "Task 1")],
+            ..DayState::default()
+        };
+        state.save(&config, &date_str).unwrap();
+
+        let synced = vec![test_support::todo("2", "Task 2")];
+        assert_eq!(todos_to_post(&config, Some(synced.clone())), synced);
+        // A failed fetch posts the last sync's todos instead of none
+        assert_eq!(todos_to_post(&config, None), state.todos);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_timezone() {
+        let directory = tempfile::tempdir().unwrap();
+        let config_path = directory.path().join("config.toml");
+
+        let config = "todoist_api_token = \"a\"\nslack_bot_token = \"b\"\n";
+        fs::write(
+            &config_path,
+            format!("{}timezone = \"Europe/Amsterdam\"\n", config),
+        )
+        .unwrap();
+        assert!(Config::load(&config_path).is_ok());
+
+        fs::write(
+            &config_path,
+            format!("{}timezone = \"Mars/Olympus\"\n", config),
+        )
+        .unwrap();
+        let error = Config::load(&config_path).unwrap_err().to_string();
+        assert!(error.contains("Unknown timezone"));
+    }
+
     #[test]
     fn test_parse_markdown_with_notes() {
         let markdown = r#"## Active Todos
@@ -1407,8 +1728,12 @@ This is preserved content.
 ## Important Links
 - https://example.com"#;
 
-        let markdown =
-            generate_markdown_content(&current_todos, &existing_todos, None, Some(notes));
+        let markdown = generate_markdown_content(
+            &current_todos,
+            &existing_todos,
+            Some(notes),
+            &MarkdownStyle::plain(),
+        );
 
         assert!(markdown.contains("- [ ] New task"));
         assert!(markdown.contains("- [x] Old task *(marked as finished)*"));
@@ -1423,8 +1748,14 @@ This is preserved content.
         let notes_without_newline = "# My Notes\nSome content";
         let notes_with_newline = "# My Notes\nSome content\n";
 
-        let markdown1 = generate_markdown_content(&[], &[], None, Some(notes_without_newline));
-        let markdown2 = generate_markdown_content(&[], &[], None, Some(notes_with_newline));
+        let markdown1 = generate_markdown_content(
+            &[],
+            &[],
+            Some(notes_without_newline),
+            &MarkdownStyle::plain(),
+        );
+        let markdown2 =
+            generate_markdown_content(&[], &[], Some(notes_with_newline), &MarkdownStyle::plain());
 
         // Both should end with exactly one newline
         assert!(markdown1.ends_with('\n'));
@@ -1529,8 +1860,12 @@ Some additional thoughts and reminders..."#;
         ];
 
         // Generate new markdown content
-        let regenerated_content =
-            generate_markdown_content(&new_todos, &parsed_todos, None, parsed_notes.as_deref());
+        let regenerated_content = generate_markdown_content(
+            &new_todos,
+            &parsed_todos,
+            parsed_notes.as_deref(),
+            &MarkdownStyle::plain(),
+        );
 
         // Verify the regenerated content
         assert!(regenerated_content.contains("## Active Todos"));
@@ -1554,4 +1889,145 @@ Some additional thoughts and reminders..."#;
         let notes_section = parts[1];
         assert!(notes_section.contains("# My Personal Notes"));
     }
+
+    #[test]
+    fn test_is_overdue() {
+        let mut todo = test_support::todo("1", "Task");
+        let today = NaiveDate::from_ymd_opt(2025, 7, 7).unwrap();
+        assert!(!is_overdue(&todo, today));
+
+        for (date, overdue) in [
+            ("2025-07-06", true),
+            ("2025-07-06T09:00:00", true),
+            ("2025-07-07", false),
+            ("2025-07-08", false),
+        ] {
+            todo.due = Some(test_support::due(date));
+            assert_eq!(is_overdue(&todo, today), overdue, "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_rich_markdown_round_trips() {
+        let todo = |id: &str, priority: u8, checked: bool, due: Option<&str>| Todo {
+            priority,
+            labels: test_support::labels(&["work"]),
+            due: due.map(test_support::due),
+            checked,
+            ..test_support::todo(id, &format!("Task {}", id))
+        };
+        let todos = vec![
+            todo("1", 4, false, Some("2025-07-06")),
+            todo("2", 1, false, None),
+            todo("3", 2, true, Some("2025-07-07")),
+        ];
+        let style = MarkdownStyle {
+            options: MarkdownOptions::all(),
+            projects: std::collections::HashMap::from([(
+                "project1".to_string(),
+                "Work".to_string(),
+            )]),
+            sections: std::collections::HashMap::new(),
+            today: NaiveDate::from_ymd_opt(2025, 7, 7).unwrap(),
+            timezone: chrono_tz::UTC,
+            template: None,
+        };
+
+        let markdown = generate_markdown_content(&todos, &[], Some("My notes"), &style);
+        assert!(
+            markdown
+                .starts_with("## 🔥 High Priority\n\n- Task 1 · 🔴 P1 · 📅 yesterday (Sun 6 Jul)")
+        );
+        assert!(markdown.contains(
+            "- [ ] Task 1 · 🔴 P1 · 📅 yesterday (Sun 6 Jul) · 🏷️ @work · 📁 Work · [↗](https://app.todoist.com/app/task/1)\n"
+        ));
+        assert!(markdown.contains("- [x] Task 3 · 🔵 P3 · 📅 today (Mon 7 Jul)"));
+        assert!(markdown.contains(
+            "## Statistics\n\n- Open: 2\n- Completed: 1\n- Progress: 33%\n- High priority: 1\n- Overdue: 1\n---\nMy notes\n"
+        ));
+
+        // Details, the high priority list and the statistics aren't parsed as todos
+        let (parsed, notes) = parse_existing_markdown(&markdown);
+        assert_eq!(
+            parsed,
+            vec![
+                ("Task 1".to_string(), false),
+                ("Task 2".to_string(), false),
+                ("Task 3".to_string(), true),
+            ]
+        );
+        assert_eq!(notes.as_deref(), Some("My notes"));
+        assert_eq!(
+            generate_markdown_content(&todos, &parsed, notes.as_deref(), &style),
+            markdown
+        );
+
+        // Slack gets the plain todos so they match their checkboxes
+        let filtered = filter_slack_metadata(&markdown);
+        assert!(filtered.contains("\n- [ ] Task 1\n"));
+        assert!(!filtered.contains("app.todoist.com"));
+    }
+
+    #[test]
+    fn test_markdown_template() {
+        let todo = |id: &str, checked: bool| Todo {
+            priority: 4,
+            checked,
+            ..test_support::todo(id, &format!("Task {}", id))
+        };
+        let todos = vec![todo("1", false), todo("2", true)];
+        let template = "# Taken voor {{ date }} ({{ stats.high_priority }} urgent)\n\n\
+            {% for task in tasks %}- [ ] {{ task.content }}{{ task.details }}\n{% endfor %}\n\
+            ## Klaar ({{ stats.completed }})\n\n\
+            {% for task in completed %}- [x] {{ task.content }}\n{% endfor %}\
+            {% if notes %}---\n{{ notes }}\n{% endif %}";
+        let style = MarkdownStyle {
+            today: NaiveDate::from_ymd_opt(2025, 7, 7).unwrap(),
+            template: Some(template.to_string()),
+            ..MarkdownStyle::plain()
+        };
+
+        let markdown = generate_markdown_content(&todos, &[], Some("My notes"), &style);
+        assert_eq!(
+            markdown,
+            "# Taken voor 2025-07-07 (1 urgent)\n\n- [ ] Task 1\n\n## Klaar (1)\n\n- [x] Task 2\n---\nMy notes\n"
+        );
+        let (parsed, notes) = parse_existing_markdown(&markdown);
+        assert_eq!(
+            parsed,
+            vec![("Task 1".to_string(), false), ("Task 2".to_string(), true)]
+        );
+        assert_eq!(notes.as_deref(), Some("My notes"));
+
+        // A broken template falls back to the built-in layout
+        let broken = MarkdownStyle {
+            template: Some("{% for %}".to_string()),
+            ..MarkdownStyle::plain()
+        };
+        assert_eq!(
+            generate_markdown_content(&todos, &[], None, &broken),
+            generate_markdown_content(&todos, &[], None, &MarkdownStyle::plain())
+        );
+    }
+
+    #[test]
+    fn test_notification_text() {
+        let todos: Vec<Todo> = [false, false, true]
+            .into_iter()
+            .enumerate()
+            .map(|(i, checked)| Todo {
+                checked,
+                ..test_support::todo(&i.to_string(), &format!("Task {}", i))
+            })
+            .collect();
+
+        assert_eq!(
+            notification_text(
+                "{open} todos left on {date} ({done} done)",
+                "2025-07-07",
+                &todos
+            ),
+            "2 todos left on 2025-07-07 (1 done)"
+        );
+    }
 }
@@ -0,0 +1,941 @@
+use crate::rollover::strip_carried_flag;
+use crate::state;
+use crate::{
+    Config, current_date, expand_tilde_path, filter_slack_metadata, is_overdue, parse_todo_lines,
+    timezone,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use chrono_tz::Tz;
+use minijinja::Environment;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use todoist::{Todo, TodoDue, TodoistClient};
+
+/// Separates a todo's content from its details, and the details from each other
+const SEPARATOR: &str = " · ";
+
+/// Where a todo's deep link points
+const TASK_URL: &str = "https://app.todoist.com/app/task/";
+
+/// What the open todos are grouped under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    Project,
+    /// Project and section
+    Section,
+    /// The first label
+    Label,
+    Priority,
+    /// Overdue, this morning, this afternoon, no time and later
+    Due,
+}
+
+/// The order of the open todos within their group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    /// The order of Todoist's Today view
+    DayOrder,
+    /// P1 first
+    Priority,
+    /// Earliest due first, todos without a due date last
+    Due,
+    /// The order within the project
+    ChildOrder,
+}
+
+/// Template files that replace the built-in layout, see the README for what they can use
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Templates {
+    /// Template of the daily markdown file
+    pub markdown: Option<String>,
+    /// Template of the Slack message text
+    pub slack: Option<String>,
+}
+
+/// An open todo as the templates see it
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskView {
+    pub id: String,
+    pub content: String,
+    /// What the built-in layout shows after the content, starting with " · "
+    pub details: String,
+    /// "P1" to "P4"
+    pub priority: String,
+    /// Like "today 14:00 (Mon 7 Jul)"
+    pub due: Option<String>,
+    pub labels: Vec<String>,
+    pub project: Option<String>,
+    pub section: Option<String>,
+    /// Link that opens the todo in Todoist
+    pub url: String,
+    /// How many parents the todo is nested under
+    pub depth: usize,
+    pub overdue: bool,
+}
+
+/// Open todos under one heading, `name` is unset if they aren't grouped
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupView {
+    pub name: Option<String>,
+    pub tasks: Vec<TaskView>,
+}
+
+/// A completed todo as the templates see it
+#[derive(Debug, Clone, Serialize)]
+pub struct CompletedView {
+    pub content: String,
+    /// Empty for todos completed in an earlier sync, whose details are no longer known
+    pub details: String,
+    /// Gone from Todoist without being seen completed
+    pub marked_as_finished: bool,
+}
+
+/// Counts shown in the statistics
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Stats {
+    pub open: usize,
+    pub completed: usize,
+    /// Percentage of the day's todos completed, unset without todos
+    pub progress: Option<usize>,
+    pub high_priority: usize,
+    pub overdue: usize,
+}
+
+/// Everything the daily markdown and Slack templates can use
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyView {
+    /// YYYY-MM-DD
+    pub date: String,
+    /// The open todos in order, also when grouped
+    pub tasks: Vec<TaskView>,
+    pub groups: Vec<GroupView>,
+    /// The open P1 todos
+    pub high_priority: Vec<TaskView>,
+    pub completed: Vec<CompletedView>,
+    pub stats: Stats,
+    /// The notes below the `---` line
+    pub notes: Option<String>,
+}
+
+/// Reconcile the current todos with the ones already in the markdown
+///
+/// Todos completed earlier stay completed, and open todos that are gone from
+/// Todoist are marked as finished. Existing todos may still carry the details
+/// they were written with, those are kept for the todos no longer in Todoist.
+pub fn daily_view(
+    current_todos: &[Todo],
+    existing_todos: &[(String, bool)],
+    notes: Option<&str>,
+    style: &MarkdownStyle,
+) -> DailyView {
+    let current_contents: HashSet<&str> = current_todos
+        .iter()
+        .map(|todo| todo.content.as_str())
+        .collect();
+    let active: Vec<&Todo> = current_todos.iter().filter(|todo| !todo.checked).collect();
+
+    let mut completed = Vec::new();
+    let mut added_completed: HashSet<&str> = HashSet::new();
+    for todo in current_todos.iter().filter(|todo| todo.checked) {
+        completed.push(CompletedView {
+            content: todo.content.clone(),
+            details: style.details(todo),
+            marked_as_finished: false,
+        });
+        added_completed.insert(&todo.content);
+    }
+    for (line, was_completed) in existing_todos {
+        let content = strip_details(line);
+        let details = &line[content.len()..];
+        let in_current = current_contents.contains(content);
+        let already_added = added_completed.contains(content);
+
+        if *was_completed && !already_added {
+            // Preserve previously completed todos (including those marked as finished)
+            completed.push(CompletedView {
+                content: content.to_string(),
+                details: details.to_string(),
+                marked_as_finished: false,
+            });
+            added_completed.insert(content);
+        } else if !*was_completed && !in_current && !already_added {
+            completed.push(CompletedView {
+                content: content.to_string(),
+                details: details.to_string(),
+                marked_as_finished: true,
+            });
+        }
+    }
+
+    let groups = style.active_groups(&active);
+    let high_priority: Vec<TaskView> = active
+        .iter()
+        .filter(|todo| todo.priority == 4)
+        .map(|todo| style.task_view(todo, 0))
+        .collect();
+    let total = active.len() + completed.len();
+    let stats = Stats {
+        open: active.len(),
+        completed: completed.len(),
+        progress: (total > 0).then(|| completed.len() * 100 / total),
+        high_priority: high_priority.len(),
+        overdue: active
+            .iter()
+            .filter(|todo| is_overdue(todo, style.today))
+            .count(),
+    };
+
+    DailyView {
+        date: style.today.format("%Y-%m-%d").to_string(),
+        tasks: groups
+            .iter()
+            .flat_map(|group| group.tasks.clone())
+            .collect(),
+        groups,
+        high_priority,
+        completed,
+        stats,
+        notes: notes.map(str::to_string),
+    }
+}
+
+/// Groups of open todos as checkbox lines, with a heading for named groups
+pub fn write_groups(groups: &[GroupView]) -> String {
+    let mut content = String::new();
+    for (index, group) in groups.iter().enumerate() {
+        if let Some(name) = &group.name {
+            if index > 0 {
+                content.push('\n');
+            }
+            content.push_str(&format!("### {}\n\n", name));
+        }
+        for task in &group.tasks {
+            content.push_str(&format!(
+                "{}- [ ] {}{}\n",
+                "  ".repeat(task.depth),
+                task.content,
+                task.details
+            ));
+        }
+    }
+    content
+}
+
+/// Read a template file, warning and falling back to the built-in layout if it can't be read
+pub fn load_template(path: Option<&str>) -> Option<String> {
+    let path = path.filter(|path| !path.is_empty())?;
+    match fs::read_to_string(expand_tilde_path(path)) {
+        Ok(source) => Some(source),
+        Err(e) => {
+            eprintln!("⚠️  Warning: Could not read template {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Read and compile a template file, for `slaist doctor`
+pub fn check_template(path: &str) -> Result<String, Box<dyn Error>> {
+    let source = fs::read_to_string(expand_tilde_path(path))?;
+    Environment::new().template_from_str(&source)?;
+    Ok(format!("{} compiles", path))
+}
+
+/// Render a template with the given context
+pub fn render_template(source: &str, context: impl Serialize) -> Result<String, minijinja::Error> {
+    let mut environment = Environment::new();
+    environment.set_keep_trailing_newline(true);
+    environment.render_str(source, context)
+}
+
+/// What the Slack template sees on top of the daily view
+#[derive(Serialize)]
+struct SlackContext<'a> {
+    #[serde(flatten)]
+    view: DailyView,
+    /// The markdown as the built-in message shows it
+    markdown: &'a str,
+    /// Whether this is the last update of a past day
+    #[serde(rename = "final")]
+    is_final: bool,
+}
+
+/// Text of a day's Slack message, from the Slack template if one is configured
+pub fn slack_text(
+    config: &Config,
+    style: &MarkdownStyle,
+    date: &str,
+    markdown: &str,
+    todos: &[Todo],
+    is_final: bool,
+) -> String {
+    let filtered = filter_slack_metadata(markdown);
+    let template = load_template(
+        config
+            .templates
+            .as_ref()
+            .and_then(|templates| templates.slack.as_deref()),
+    );
+    if let Some(template) = template {
+        let (existing_todos, notes) = parse_todo_lines(markdown);
+        let context = SlackContext {
+            view: daily_view(todos, &existing_todos, notes.as_deref(), style),
+            markdown: &filtered,
+            is_final,
+        };
+        match render_template(&template, context) {
+            Ok(text) => return text,
+            Err(e) => eprintln!(
+                "⚠️  Warning: Could not render the Slack template, using the default layout: {}",
+                e
+            ),
+        }
+    }
+
+    let suffix = if is_final { " (final)" } else { "" };
+    format!("📅 *Daily Todos - {}*{}\n\n{}", date, suffix, filtered)
+}
+
+/// What the daily markdown shows besides the todos themselves, all off by default
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownOptions {
+    /// Marker for priorities P1 to P3
+    pub priority: bool,
+    /// Due date relative to today, with the date and time
+    pub due: bool,
+    pub labels: bool,
+    /// Project and section names
+    pub project: bool,
+    /// Link that opens the todo in Todoist
+    pub links: bool,
+    /// Section listing the open P1 todos at the top
+    pub high_priority: bool,
+    /// Counts at the bottom, above the notes
+    pub stats: bool,
+    /// Headings the open todos are listed under, one flat list if unset
+    pub group_by: Option<GroupBy>,
+    /// Order of the open todos, the order Todoist returns them in if unset
+    pub sort_by: Option<SortBy>,
+    /// List subtasks indented under their parent
+    pub nest: bool,
+}
+
+#[cfg(test)]
+impl MarkdownOptions {
+    /// Every detail and section, with subtasks nested but no grouping or sorting
+    pub fn all() -> Self {
+        Self {
+            priority: true,
+            due: true,
+            labels: true,
+            project: true,
+            links: true,
+            high_priority: true,
+            stats: true,
+            group_by: None,
+            sort_by: None,
+            nest: true,
+        }
+    }
+}
+
+/// Project names by ID, empty if Todoist can't be reached
+pub async fn project_names(config: &Config) -> HashMap<String, String> {
+    let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+    match client.get_projects().await {
+        Ok(projects) => projects.into_iter().map(|p| (p.id, p.name)).collect(),
+        Err(e) => {
+            eprintln!("⚠️  Warning: Could not fetch Todoist projects: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Everything needed to render the details of todos
+#[derive(Debug, Clone)]
+pub struct MarkdownStyle {
+    pub options: MarkdownOptions,
+    /// Project names by ID
+    pub projects: HashMap<String, String>,
+    /// Section names by ID
+    pub sections: HashMap<String, String>,
+    /// Day due dates are relative to
+    pub today: NaiveDate,
+    /// Timezone due times are shown in
+    pub timezone: Tz,
+    /// Template replacing the built-in layout of the markdown file
+    pub template: Option<String>,
+}
+
+impl MarkdownStyle {
+    /// Render nothing but the todos
+    pub fn plain() -> Self {
+        Self {
+            options: MarkdownOptions::default(),
+            projects: HashMap::new(),
+            sections: HashMap::new(),
+            today: NaiveDate::default(),
+            timezone: Tz::UTC,
+            template: None,
+        }
+    }
+
+    /// Style from the `[markdown]` config, without any names
+    fn from_config(config: &Config) -> Self {
+        Self {
+            options: config.markdown.clone().unwrap_or_default(),
+            today: current_date(config),
+            timezone: timezone(config),
+            template: load_template(
+                config
+                    .templates
+                    .as_ref()
+                    .and_then(|templates| templates.markdown.as_deref()),
+            ),
+            ..Self::plain()
+        }
+    }
+
+    /// Style from the `[markdown]` config with the names of the last sync, without
+    /// going through Todoist
+    pub fn cached(config: &Config) -> Self {
+        let mut style = Self::from_config(config);
+        if style.options.project {
+            let names = state::cached_names(config);
+            style.projects = names.projects;
+            style.sections = names.sections;
+        }
+        style
+    }
+
+    /// Style from the `[markdown]` config, fetching project and section names if they're shown
+    pub async fn load(config: &Config) -> Self {
+        let mut style = Self::from_config(config);
+        if !style.options.project {
+            return style;
+        }
+
+        style.projects = project_names(config).await;
+        let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+        match client.get_sections().await {
+            Ok(sections) => style.sections = sections.into_iter().map(|s| (s.id, s.name)).collect(),
+            Err(e) => eprintln!("⚠️  Warning: Could not fetch Todoist sections: {}", e),
+        }
+
+        if !style.projects.is_empty() {
+            let names = state::Names {
+                projects: style.projects.clone(),
+                sections: style.sections.clone(),
+            };
+            if let Err(e) = state::save_names(config, &names) {
+                eprintln!("⚠️  Warning: Could not save the Todoist names: {}", e);
+            }
+        }
+        style
+    }
+
+    /// The details shown after a todo's content, starting with the separator
+    pub fn details(&self, todo: &Todo) -> String {
+        let options = &self.options;
+        let mut details = Vec::new();
+
+        if options.priority {
+            if let Some(marker) = priority_marker(todo.priority) {
+                details.push(marker.to_string());
+            }
+        }
+        if options.due {
+            if let Some(due) = &todo.due {
+                details.push(format!("📅 {}", self.due_text(due)));
+            }
+        }
+        if options.labels && !todo.labels.is_empty() {
+            let labels: Vec<String> = todo.labels.iter().map(|l| format!("@{}", l)).collect();
+            details.push(format!("🏷️ {}", labels.join(" ")));
+        }
+        if options.project {
+            let project = self.projects.get(&todo.project_id);
+            let section = todo
+                .section_id
+                .as_ref()
+                .and_then(|id| self.sections.get(id));
+            match (project, section) {
+                (Some(project), Some(section)) => {
+                    details.push(format!("📁 {} › {}", project, section))
+                }
+                (Some(project), None) => details.push(format!("📁 {}", project)),
+                _ => {}
+            }
+        }
+        if options.links {
+            details.push(format!("[↗]({}{})", TASK_URL, todo.id));
+        }
+
+        details
+            .iter()
+            .map(|detail| format!("{}{}", SEPARATOR, detail))
+            .collect()
+    }
+
+    /// The open todos grouped, sorted and nested as configured, each group
+    /// listing subtasks right after their parent
+    pub fn active_groups(&self, todos: &[&Todo]) -> Vec<GroupView> {
+        let ids: HashSet<&str> = todos.iter().map(|todo| todo.id.as_str()).collect();
+        let mut roots: Vec<&Todo> = todos
+            .iter()
+            .copied()
+            .filter(|todo| !self.options.nest || !has_parent_in(todo, &ids))
+            .collect();
+        self.sort(&mut roots);
+
+        let Some(group_by) = self.options.group_by else {
+            let mut tasks = Vec::new();
+            for todo in roots {
+                self.push_tree(&mut tasks, todo, todos, 0);
+            }
+            return vec![GroupView { name: None, tasks }];
+        };
+
+        let mut groups: BTreeMap<(u8, String), Vec<&Todo>> = BTreeMap::new();
+        for todo in roots {
+            groups
+                .entry(self.group(group_by, todo))
+                .or_default()
+                .push(todo);
+        }
+        groups
+            .into_iter()
+            .map(|((_, name), group)| {
+                let mut tasks = Vec::new();
+                for todo in group {
+                    self.push_tree(&mut tasks, todo, todos, 0);
+                }
+                GroupView {
+                    name: Some(name),
+                    tasks,
+                }
+            })
+            .collect()
+    }
+
+    /// Add a todo and, if nesting, its subtasks below it
+    fn push_tree(&self, tasks: &mut Vec<TaskView>, todo: &Todo, todos: &[&Todo], depth: usize) {
+        tasks.push(self.task_view(todo, depth));
+        if !self.options.nest {
+            return;
+        }
+        let mut children: Vec<&Todo> = todos
+            .iter()
+            .copied()
+            .filter(|child| child.parent_id.as_deref() == Some(todo.id.as_str()))
+            .collect();
+        self.sort(&mut children);
+        for child in children {
+            self.push_tree(tasks, child, todos, depth + 1);
+        }
+    }
+
+    /// A todo as the templates see it
+    pub fn task_view(&self, todo: &Todo, depth: usize) -> TaskView {
+        TaskView {
+            id: todo.id.clone(),
+            content: todo.content.clone(),
+            details: self.details(todo),
+            priority: priority_name(todo.priority).to_string(),
+            due: todo.due.as_ref().map(|due| self.due_text(due)),
+            labels: todo.labels.clone(),
+            project: self.projects.get(&todo.project_id).cloned(),
+            section: todo
+                .section_id
+                .as_ref()
+                .and_then(|id| self.sections.get(id))
+                .cloned(),
+            url: format!("{}{}", TASK_URL, todo.id),
+            depth,
+            overdue: is_overdue(todo, self.today),
+        }
+    }
+
+    fn sort(&self, todos: &mut [&Todo]) {
+        let Some(sort_by) = self.options.sort_by else {
+            return;
+        };
+        match sort_by {
+            SortBy::DayOrder => {
+                todos.sort_by_key(|todo| (todo.day_order.is_none(), todo.day_order))
+            }
+            SortBy::Priority => todos.sort_by_key(|todo| std::cmp::Reverse(todo.priority)),
+            SortBy::Due => todos.sort_by_key(|todo| {
+                let due = todo.due.as_ref().and_then(|due| due_at(due, self.timezone));
+                (due.is_none(), due)
+            }),
+            SortBy::ChildOrder => todos.sort_by_key(|todo| todo.child_order),
+        }
+    }
+
+    /// The heading a todo is grouped under, with a rank that orders the headings
+    fn group(&self, group_by: GroupBy, todo: &Todo) -> (u8, String) {
+        let project = self.projects.get(&todo.project_id);
+        match group_by {
+            GroupBy::Project => match project {
+                Some(project) => (0, project.clone()),
+                None => (1, "Other".to_string()),
+            },
+            GroupBy::Section => {
+                let section = todo
+                    .section_id
+                    .as_ref()
+                    .and_then(|id| self.sections.get(id));
+                match (project, section) {
+                    (Some(project), Some(section)) => (0, format!("{} › {}", project, section)),
+                    (Some(project), None) => (0, project.clone()),
+                    _ => (1, "Other".to_string()),
+                }
+            }
+            GroupBy::Label => match todo.labels.first() {
+                Some(label) => (0, format!("@{}", label)),
+                None => (1, "No label".to_string()),
+            },
+            GroupBy::Priority => match priority_marker(todo.priority) {
+                Some(marker) => (4 - todo.priority, marker.to_string()),
+                None => (3, "P4".to_string()),
+            },
+            GroupBy::Due => {
+                let due = todo.due.as_ref().and_then(|due| due_at(due, self.timezone));
+                match due {
+                    Some((date, _)) if date < self.today => (0, "Overdue".to_string()),
+                    Some((date, Some(time))) if date == self.today && time.hour() < 12 => {
+                        (1, "This morning".to_string())
+                    }
+                    Some((date, Some(_))) if date == self.today => {
+                        (2, "This afternoon".to_string())
+                    }
+                    Some((date, _)) if date > self.today => (4, "Later".to_string()),
+                    _ => (3, "No time".to_string()),
+                }
+            }
+        }
+    }
+
+    /// "today 14:00 (Mon 7 Jul)", "in 3 days (Thu 10 Jul)" or "2 days ago (Sat 5 Jul)"
+    fn due_text(&self, due: &TodoDue) -> String {
+        let Some((date, time)) = due_at(due, self.timezone) else {
+            return due.string.clone();
+        };
+        let relative = match (date - self.today).num_days() {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            -1 => "yesterday".to_string(),
+            days if days > 1 => format!("in {} days", days),
+            days => format!("{} days ago", -days),
+        };
+        match time {
+            Some(time) => format!(
+                "{} {} ({})",
+                relative,
+                time.format("%H:%M"),
+                date.format("%a %-d %b")
+            ),
+            None => format!("{} ({})", relative, date.format("%a %-d %b")),
+        }
+    }
+}
+
+fn has_parent_in(todo: &Todo, ids: &HashSet<&str>) -> bool {
+    todo.parent_id
+        .as_deref()
+        .is_some_and(|parent| ids.contains(parent))
+}
+
+/// "P1" to "P4" for Todoist's priorities, which the API numbers 4 to 1
+fn priority_name(priority: u8) -> &'static str {
+    match priority {
+        4 => "P1",
+        3 => "P2",
+        2 => "P3",
+        _ => "P4",
+    }
+}
+
+/// Marker for Todoist's P1 to P3, which the API numbers 4 to 2
+pub fn priority_marker(priority: u8) -> Option<&'static str> {
+    match priority {
+        4 => Some("🔴 P1"),
+        3 => Some("🟠 P2"),
+        2 => Some("🔵 P3"),
+        _ => None,
+    }
+}
+
+/// When a todo is due, in `timezone`: the day, and the time if it has one
+///
+/// Due times with a timezone come as UTC, floating ones as local time.
+pub fn due_at(due: &TodoDue, timezone: Tz) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let datetime = due
+        .datetime
+        .as_deref()
+        .or(Some(due.date.as_str()).filter(|date| date.len() > 10));
+    if let Some(datetime) = datetime {
+        if let Ok(utc) = DateTime::parse_from_rfc3339(datetime) {
+            let local = utc.with_timezone(&timezone).naive_local();
+            return Some((local.date(), Some(local.time())));
+        }
+        if let Ok(local) = NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M:%S") {
+            return Some((local.date(), Some(local.time())));
+        }
+    }
+    let date = NaiveDate::parse_from_str(due.date.get(..10)?, "%Y-%m-%d").ok()?;
+    Some((date, None))
+}
+
+fn is_detail(segment: &str) -> bool {
+    ["🔴 P1", "🟠 P2", "🔵 P3"].contains(&segment)
+        || segment.starts_with("📅 ")
+        || segment.starts_with("🏷️ ")
+        || segment.starts_with("📁 ")
+        || (segment.starts_with("[↗](") && segment.ends_with(')'))
+}
+
+/// A todo's content without the details rendered after it
+pub fn strip_details(content: &str) -> &str {
+    let mut end = content.len();
+    while let Some(start) = content[..end].rfind(SEPARATOR) {
+        if !is_detail(&content[start + SEPARATOR.len()..end]) {
+            break;
+        }
+        end = start;
+    }
+    &content[..end]
+}
+
+/// A markdown list line without the todo details and carried-over flag, so Slack
+/// can match it to its task and show its own icons instead
+pub fn strip_line_details(line: &str) -> String {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+    for prefix in ["- [ ] ", "- [x] ", "- "] {
+        if let Some(rest) = content.strip_prefix(prefix) {
+            let rest = strip_details(strip_carried_flag(rest));
+            return format!("{}{}{}", indent, prefix, rest);
+        }
+    }
+    line.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(due: Option<TodoDue>) -> Todo {
+        Todo {
+            section_id: Some("section1".to_string()),
+            priority: 4,
+            labels: test_support::labels(&["work", "docs"]),
+            due,
+            ..test_support::todo("123", "Write docs")
+        }
+    }
+
+    fn due(date: &str, datetime: Option<&str>) -> TodoDue {
+        TodoDue {
+            datetime: datetime.map(str::to_string),
+            string: "some day".to_string(),
+            ..test_support::due(date)
+        }
+    }
+
+    fn style() -> MarkdownStyle {
+        MarkdownStyle {
+            options: MarkdownOptions::all(),
+            projects: HashMap::from([("project1".to_string(), "Work".to_string())]),
+            sections: HashMap::from([("section1".to_string(), "Planning".to_string())]),
+            today: NaiveDate::from_ymd_opt(2025, 7, 7).unwrap(),
+            timezone: chrono_tz::Europe::Amsterdam,
+            template: None,
+        }
+    }
+
+    #[test]
+    fn test_details() {
+        let details = style().details(&todo(Some(due("2025-07-07", Some("2025-07-07T12:00:00Z")))));
+        assert_eq!(
+            details,
+            " · 🔴 P1 · 📅 today 14:00 (Mon 7 Jul) · 🏷️ @work @docs · 📁 Work › Planning · [↗](https://app.todoist.com/app/task/123)"
+        );
+
+        let plain = MarkdownStyle {
+            options: MarkdownOptions::default(),
+            ..style()
+        };
+        assert_eq!(plain.details(&todo(None)), "");
+    }
+
+    #[test]
+    fn test_due_text() {
+        let style = style();
+        let text = |date: &str, datetime: Option<&str>| style.due_text(&due(date, datetime));
+
+        assert_eq!(text("2025-07-08", None), "tomorrow (Tue 8 Jul)");
+        assert_eq!(text("2025-07-06", None), "yesterday (Sun 6 Jul)");
+        assert_eq!(text("2025-07-10", None), "in 3 days (Thu 10 Jul)");
+        assert_eq!(text("2025-07-05", None), "2 days ago (Sat 5 Jul)");
+        // Floating times stay as they are
+        assert_eq!(text("2025-07-07T09:30:00", None), "today 09:30 (Mon 7 Jul)");
+        assert_eq!(text("invalid", None), "some day");
+    }
+
+    fn task(id: &str, parent: Option<&str>, priority: u8, due: Option<&str>) -> Todo {
+        Todo {
+            project_id: if id.starts_with('h') {
+                "home"
+            } else {
+                "project1"
+            }
+            .to_string(),
+            parent_id: parent.map(str::to_string),
+            priority,
+            due: due.map(test_support::due),
+            child_order: id.len() as u32,
+            ..test_support::todo(id, &format!("Task {}", id))
+        }
+    }
+
+    fn list_style(group_by: Option<GroupBy>, sort_by: Option<SortBy>) -> MarkdownStyle {
+        MarkdownStyle {
+            options: MarkdownOptions {
+                group_by,
+                sort_by,
+                nest: true,
+                ..MarkdownOptions::default()
+            },
+            ..style()
+        }
+    }
+
+    #[test]
+    fn test_active_groups_nests_subtasks() {
+        let todos = [
+            task("1", None, 1, None),
+            task("11", Some("1"), 1, None),
+            task("2", None, 1, None),
+            task("111", Some("11"), 1, None),
+            task("3", Some("closed"), 1, None),
+        ];
+        let todos: Vec<&Todo> = todos.iter().collect();
+
+        assert_eq!(
+            write_groups(&list_style(None, None).active_groups(&todos)),
+            "- [ ] Task 1\n  - [ ] Task 11\n    - [ ] Task 111\n- [ ] Task 2\n- [ ] Task 3\n"
+        );
+
+        let mut flat = list_style(None, None);
+        flat.options.nest = false;
+        assert_eq!(write_groups(&flat.active_groups(&todos)).lines().count(), 5);
+        assert!(!write_groups(&flat.active_groups(&todos)).contains("  - [ ]"));
+    }
+
+    #[test]
+    fn test_active_groups_sorts() {
+        let todos = [
+            task("1", None, 1, Some("2025-07-08")),
+            task("2", None, 4, None),
+            task("3", None, 3, Some("2025-07-07T09:00:00")),
+        ];
+        // Today view order, with the todo that isn't in it last
+        let day_orders = [Some(2), Some(1), None];
+        let todos: Vec<Todo> = todos
+            .into_iter()
+            .zip(day_orders)
+            .map(|(todo, day_order)| Todo { day_order, ..todo })
+            .collect();
+        let todos: Vec<&Todo> = todos.iter().collect();
+        let order = |sort_by| {
+            write_groups(&list_style(None, Some(sort_by)).active_groups(&todos))
+                .lines()
+                .map(|line| line.trim_start_matches("- [ ] Task ").to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        assert_eq!(order(SortBy::Priority), "2,3,1");
+        assert_eq!(order(SortBy::Due), "3,1,2");
+        assert_eq!(order(SortBy::DayOrder), "2,1,3");
+    }
+
+    #[test]
+    fn test_active_groups_groups() {
+        let todos = [
+            task("1", None, 1, Some("2025-07-06")),
+            task("2", None, 4, Some("2025-07-07T15:00:00")),
+            task("h3", None, 3, Some("2025-07-07T09:00:00")),
+            task("h4", None, 1, None),
+            task("21", Some("2"), 1, Some("2025-07-09")),
+        ];
+        let todos: Vec<&Todo> = todos.iter().collect();
+
+        assert_eq!(
+            write_groups(&list_style(Some(GroupBy::Due), None).active_groups(&todos)),
+            "### Overdue\n\n- [ ] Task 1\n\n### This morning\n\n- [ ] Task h3\n\n### This afternoon\n\n- [ ] Task 2\n  - [ ] Task 21\n\n### No time\n\n- [ ] Task h4\n"
+        );
+        assert_eq!(
+            write_groups(&list_style(Some(GroupBy::Project), None).active_groups(&todos)),
+            "### Work\n\n- [ ] Task 1\n- [ ] Task 2\n  - [ ] Task 21\n\n### Other\n\n- [ ] Task h3\n- [ ] Task h4\n"
+        );
+        let by_priority =
+            write_groups(&list_style(Some(GroupBy::Priority), None).active_groups(&todos));
+        let headings: Vec<&str> = by_priority
+            .lines()
+            .filter(|line| line.starts_with("###"))
+            .collect();
+        assert_eq!(headings, ["### 🔴 P1", "### 🟠 P2", "### P4"]);
+    }
+
+    #[test]
+    fn test_daily_view_keeps_existing_details() {
+        let existing = [
+            ("Done earlier · 🔴 P1 · 📁 Work".to_string(), true),
+            ("Gone · 📁 Home".to_string(), false),
+        ];
+        let view = daily_view(&[], &existing, None, &style());
+        let completed: Vec<(&str, &str, bool)> = view
+            .completed
+            .iter()
+            .map(|todo| {
+                (
+                    todo.content.as_str(),
+                    todo.details.as_str(),
+                    todo.marked_as_finished,
+                )
+            })
+            .collect();
+        assert_eq!(
+            completed,
+            [
+                ("Done earlier", " · 🔴 P1 · 📁 Work", false),
+                ("Gone", " · 📁 Home", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_details() {
+        let content = format!(
+            "Write docs{}",
+            style().details(&todo(Some(due("2025-07-07", None))))
+        );
+        assert_eq!(strip_details(&content), "Write docs");
+        assert_eq!(strip_details("Read · Write"), "Read · Write");
+        assert_eq!(strip_details("Read · Write · 🔴 P1"), "Read · Write");
+        assert_eq!(strip_details("Plain"), "Plain");
+        assert_eq!(
+            strip_line_details("  - [ ] Write docs · 🔴 P1 *(carried 2 days)*"),
+            "  - [ ] Write docs"
+        );
+        assert_eq!(strip_line_details("## Active Todos"), "## Active Todos");
+    }
+}
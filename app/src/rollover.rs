@@ -0,0 +1,327 @@
+use crate::render::{self, MarkdownStyle};
+use crate::state::DayState;
+use crate::{
+    Config, destinations, generate_markdown_content, get_todos_directory, parse_existing_markdown,
+    parse_todo_lines,
+};
+use chrono::{NaiveDate, Utc};
+use slack::{SlackClient, SlackMessage};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use todoist::Todo;
+
+/// What a new day takes over from the day before it
+#[derive(Debug, Default, PartialEq)]
+pub struct CarriedOver {
+    /// Open todos and the number of days each has been carried
+    pub todos: BTreeMap<String, u32>,
+    /// Notes section of the previous day, if `carry_over_notes` is on
+    pub notes: Option<String>,
+}
+
+fn carried_flag(days: u32) -> String {
+    match days {
+        1 => " *(carried 1 day)*".to_string(),
+        days => format!(" *(carried {} days)*", days),
+    }
+}
+
+/// Remove the `*(carried n days)*` flag from a todo's content
+pub fn strip_carried_flag(content: &str) -> &str {
+    if let Some(start) = content.rfind(" *(carried ") {
+        let flag = &content[start..];
+        if flag.ends_with(" day)*") || flag.ends_with(" days)*") {
+            return content[..start].trim_end();
+        }
+    }
+    content
+}
+
+/// Flag the open todos carried over from earlier days with how long they've carried
+pub fn flag_carried(markdown: &str, carried: &BTreeMap<String, u32>) -> String {
+    if carried.is_empty() {
+        return markdown.to_string();
+    }
+
+    let mut in_notes = false;
+    let mut flagged = markdown
+        .lines()
+        .map(|line| {
+            if line.trim() == "---" {
+                in_notes = true;
+            }
+            let days = line
+                .trim_start()
+                .strip_prefix("- [ ] ")
+                .filter(|_| !in_notes)
+                .and_then(|content| carried.get(render::strip_details(content)));
+            match days {
+                Some(days) => format!("{}{}", line, carried_flag(*days)),
+                None => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if markdown.ends_with('\n') {
+        flagged.push('\n');
+    }
+    flagged
+}
+
+/// The most recent day before `date` that has a markdown file
+pub fn previous_day(config: &Config, date: NaiveDate) -> Option<NaiveDate> {
+    fs::read_dir(get_todos_directory(config))
+        .ok()?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            let stem = name.to_str()?.strip_suffix(".md")?.to_string();
+            NaiveDate::parse_from_str(&stem, "%Y-%m-%d").ok()
+        })
+        .filter(|day| *day < date)
+        .max()
+}
+
+/// Reconcile a day's markdown one last time against the todos still known to
+/// Todoist, without adding the todos of other days
+fn finalize_markdown(
+    content: &str,
+    todos: &[Todo],
+    carried: &BTreeMap<String, u32>,
+    style: &MarkdownStyle,
+) -> String {
+    let (existing_todos, notes) = parse_todo_lines(content);
+    let todos = day_todos(content, todos);
+    flag_carried(
+        &generate_markdown_content(&todos, &existing_todos, notes.as_deref(), style),
+        carried,
+    )
+}
+
+/// The todos that are listed in a day's markdown
+fn day_todos(content: &str, todos: &[Todo]) -> Vec<Todo> {
+    let (existing_todos, _) = parse_existing_markdown(content);
+    let contents: HashSet<&str> = existing_todos
+        .iter()
+        .map(|(content, _)| content.as_str())
+        .collect();
+    todos
+        .iter()
+        .filter(|todo| contents.contains(todo.content.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Work out which todos carry over: those still open at the end of the
+/// previous day that Todoist still has open
+fn carry_over(
+    final_markdown: &str,
+    todos: &[Todo],
+    carried: &BTreeMap<String, u32>,
+) -> BTreeMap<String, u32> {
+    let open: HashSet<&str> = todos
+        .iter()
+        .filter(|todo| !todo.checked)
+        .map(|todo| todo.content.as_str())
+        .collect();
+    parse_existing_markdown(final_markdown)
+        .0
+        .into_iter()
+        .filter(|(content, done)| !done && open.contains(content.as_str()))
+        .map(|(content, _)| {
+            let days = carried.get(&content).map_or(1, |days| days + 1);
+            (content, days)
+        })
+        .collect()
+}
+
+/// Update the previous day's Slack messages one last time, marked as final
+async fn finalize_slack(
+    config: &Config,
+    date: &str,
+    markdown: &str,
+    todos: &[Todo],
+    style: &MarkdownStyle,
+    state: &mut DayState,
+) {
+    let webhook = config
+        .slack_webhook_url
+        .as_deref()
+        .is_some_and(|url| !url.is_empty());
+    if webhook || state.messages.is_empty() {
+        return;
+    }
+
+    let client = match SlackClient::with_bot_token(config.slack_bot_token.clone()) {
+        Ok(client) => client
+            .with_icons(config.slack_icons.clone().unwrap_or_default())
+            .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default()),
+        Err(e) => {
+            eprintln!("❌ Error creating Slack client: {}", e);
+            return;
+        }
+    };
+
+    // Only update what was posted: a new message for a day that's over would be noise
+    let todos = day_todos(markdown, todos);
+    let message = SlackMessage::new(render::slack_text(
+        config, style, date, markdown, &todos, true,
+    ));
+    destinations::update_all(&client, &message, &mut state.messages).await;
+}
+
+/// Finalize the last day before `date` and return what carries over to it
+pub async fn roll_over(
+    config: &Config,
+    date: NaiveDate,
+    todos: &[Todo],
+    style: &MarkdownStyle,
+) -> CarriedOver {
+    let Some(previous) = previous_day(config, date) else {
+        return CarriedOver::default();
+    };
+    let previous_str = previous.format("%Y-%m-%d").to_string();
+    let file_path = get_todos_directory(config).join(format!("{}.md", previous_str));
+    let Ok(content) = fs::read_to_string(&file_path) else {
+        return CarriedOver::default();
+    };
+    let mut state = DayState::load(config, &previous_str);
+
+    let final_markdown = if state.finalized.is_some() {
+        content
+    } else {
+        println!("🌙 Finalizing {}", previous_str);
+        let final_markdown = finalize_markdown(&content, todos, &state.carried, style);
+        if let Err(e) = fs::write(&file_path, &final_markdown) {
+            eprintln!(
+                "⚠️  Warning: Could not write to file {}: {}",
+                file_path.display(),
+                e
+            );
+        }
+        finalize_slack(
+            config,
+            &previous_str,
+            &final_markdown,
+            todos,
+            style,
+            &mut state,
+        )
+        .await;
+        state.finalized = Some(Utc::now());
+        if let Err(e) = state.save(config, &previous_str) {
+            eprintln!(
+                "⚠️  Warning: Could not save the state of {}: {}",
+                previous_str, e
+            );
+        }
+        final_markdown
+    };
+
+    let carried = CarriedOver {
+        todos: carry_over(&final_markdown, todos, &state.carried),
+        notes: if config.carry_over_notes.unwrap_or(false) {
+            parse_existing_markdown(&final_markdown).1
+        } else {
+            None
+        },
+    };
+    if !carried.todos.is_empty() {
+        println!(
+            "📦 Carrying {} open todos over from {}",
+            carried.todos.len(),
+            previous_str
+        );
+    }
+    carried
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(content: &str, checked: bool) -> Todo {
+        Todo {
+            checked,
+            ..test_support::todo(content, content)
+        }
+    }
+
+    #[test]
+    fn test_flag_carried_round_trips() {
+        let carried = BTreeMap::from([("Task A".to_string(), 1), ("Task B".to_string(), 3)]);
+        let markdown = "## Active Todos\n\n- [ ] Task A\n- [ ] Task B\n- [ ] Task C\n\n## Completed Todos\n\n- [x] Task D\n---\n- [ ] Task A\n";
+        let flagged = flag_carried(markdown, &carried);
+
+        assert!(flagged.contains("- [ ] Task A *(carried 1 day)*\n"));
+        assert!(flagged.contains("- [ ] Task B *(carried 3 days)*\n"));
+        assert!(flagged.contains("- [ ] Task C\n"));
+        assert!(flagged.ends_with("---\n- [ ] Task A\n"));
+
+        let (todos, _) = parse_existing_markdown(&flagged);
+        assert_eq!(todos[0], ("Task A".to_string(), false));
+        assert_eq!(todos[1], ("Task B".to_string(), false));
+        assert_eq!(
+            strip_carried_flag("Read *(carried away)*"),
+            "Read *(carried away)*"
+        );
+    }
+
+    #[test]
+    fn test_finalize_and_carry_over() {
+        let content = "## Active Todos\n\n- [ ] Task A *(carried 2 days)*\n- [ ] Task B\n- [ ] Task C\n\n## Completed Todos\n\n- [x] Task D\n---\nRemember the milk\n";
+        let previous_carried = BTreeMap::from([("Task A".to_string(), 2)]);
+        // Task B was completed late, Task C is gone, Task E is new today
+        let todos = vec![
+            todo("Task A", false),
+            todo("Task B", true),
+            todo("Task E", false),
+        ];
+
+        let final_markdown =
+            finalize_markdown(content, &todos, &previous_carried, &MarkdownStyle::plain());
+        assert!(final_markdown.contains("- [ ] Task A *(carried 2 days)*"));
+        assert!(final_markdown.contains("- [x] Task B"));
+        assert!(final_markdown.contains("- [x] Task C *(marked as finished)*"));
+        assert!(!final_markdown.contains("Task E"));
+        assert!(final_markdown.ends_with("---\nRemember the milk\n"));
+
+        let carried = carry_over(&final_markdown, &todos, &previous_carried);
+        assert_eq!(carried, BTreeMap::from([("Task A".to_string(), 3)]));
+    }
+
+    #[tokio::test]
+    async fn test_roll_over_finalizes_the_previous_file_once() {
+        let directory = tempfile::tempdir().unwrap();
+        let directory = directory.path();
+        let config = Config {
+            todos_directory: Some(directory.to_string_lossy().to_string()),
+            carry_over_notes: Some(true),
+            ..Config::default()
+        };
+        fs::write(
+            directory.join("2025-07-03.md"),
+            "## Active Todos\n\n- [ ] Old\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("2025-07-04.md"),
+            "## Active Todos\n\n- [ ] Task A\n\n## Completed Todos\n\n---\nNotes\n",
+        )
+        .unwrap();
+        let monday = NaiveDate::from_ymd_opt(2025, 7, 7).unwrap();
+        let todos = vec![todo("Task A", false)];
+
+        let carried = roll_over(&config, monday, &todos, &MarkdownStyle::plain()).await;
+        assert_eq!(carried.todos, BTreeMap::from([("Task A".to_string(), 1)]));
+        assert_eq!(carried.notes.as_deref(), Some("Notes"));
+        assert!(DayState::load(&config, "2025-07-04").finalized.is_some());
+        assert!(DayState::load(&config, "2025-07-03").finalized.is_none());
+
+        // Running again doesn't finalize twice but carries the same todos
+        assert_eq!(
+            roll_over(&config, monday, &todos, &MarkdownStyle::plain()).await,
+            carried
+        );
+    }
+}
@@ -0,0 +1,444 @@
+use crate::state::DayState;
+use crate::{
+    Config, destinations, expand_tilde_path, get_todos_directory, local_now,
+    parse_existing_markdown, timezone,
+};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use slack::{SlackMessage, WebhookClient};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+/// How long after its time a rule still runs if watch mode started after it
+const GRACE_PERIOD: chrono::TimeDelta = chrono::TimeDelta::minutes(15);
+
+/// What a scheduled rule does when it fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledAction {
+    /// Post the day's message; before that, watch mode only syncs the file
+    Post,
+    /// Update the message one last time and reply with an end-of-day summary
+    Summary,
+}
+
+impl fmt::Display for ScheduledAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduledAction::Post => write!(f, "post"),
+            ScheduledAction::Summary => write!(f, "summary"),
+        }
+    }
+}
+
+/// Run an action at a local time on some days of the week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleRule {
+    pub action: ScheduledAction,
+    /// Time as HH:MM in the configured timezone
+    pub time: String,
+    /// Days like "mon" or ranges like "mon-fri", every day if left out
+    #[serde(default)]
+    pub days: Option<Vec<String>>,
+}
+
+impl ScheduleRule {
+    /// Identifies the rule among the runs fired on a day
+    pub fn key(&self) -> String {
+        format!("{}@{}", self.action, self.time)
+    }
+
+    fn parse_time(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(&self.time, "%H:%M").ok()
+    }
+
+    fn runs_on(&self, weekday: Weekday) -> bool {
+        match &self.days {
+            None => true,
+            Some(days) => days
+                .iter()
+                .any(|days| parse_days(days).is_ok_and(|days| days.contains(&weekday))),
+        }
+    }
+}
+
+/// When watch mode posts, and which days it skips
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(default)]
+    pub rules: Vec<ScheduleRule>,
+    /// File with one YYYY-MM-DD date per line on which no rules run
+    #[serde(default)]
+    pub holidays_file: Option<String>,
+}
+
+impl Schedule {
+    /// Whether any rule runs the action
+    pub fn has(&self, action: ScheduledAction) -> bool {
+        self.rules.iter().any(|rule| rule.action == action)
+    }
+}
+
+/// Parse a day like "mon" or a range like "mon-fri"
+fn parse_days(days: &str) -> Result<Vec<Weekday>, String> {
+    let parse = |day: &str| {
+        day.trim()
+            .parse::<Weekday>()
+            .map_err(|_| format!("Unknown day {:?}", day))
+    };
+
+    match days.split_once('-') {
+        None => Ok(vec![parse(days)?]),
+        Some((first, last)) => {
+            let (mut day, last) = (parse(first)?, parse(last)?);
+            let mut range = vec![day];
+            while day != last {
+                day = day.succ();
+                range.push(day);
+            }
+            Ok(range)
+        }
+    }
+}
+
+/// Parse the holidays file, ignoring blank lines and `#` comments
+fn parse_holidays(content: &str) -> HashSet<NaiveDate> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match NaiveDate::parse_from_str(line, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                eprintln!("⚠️  Warning: Ignoring invalid holiday {:?}", line);
+                None
+            }
+        })
+        .collect()
+}
+
+fn load_holidays(schedule: &Schedule) -> HashSet<NaiveDate> {
+    let Some(path) = &schedule.holidays_file else {
+        return HashSet::new();
+    };
+    match fs::read_to_string(expand_tilde_path(path)) {
+        Ok(content) => parse_holidays(&content),
+        Err(e) => {
+            eprintln!("⚠️  Warning: Could not read holidays file {}: {}", path, e);
+            HashSet::new()
+        }
+    }
+}
+
+/// The valid rules that run on a day, with their parsed times
+fn rules_on<'a>(
+    schedule: &'a Schedule,
+    date: NaiveDate,
+    holidays: &HashSet<NaiveDate>,
+) -> Vec<(&'a ScheduleRule, NaiveTime)> {
+    if holidays.contains(&date) {
+        return Vec::new();
+    }
+    schedule
+        .rules
+        .iter()
+        .filter_map(|rule| match rule.parse_time() {
+            Some(time) => Some((rule, time)),
+            None => {
+                eprintln!(
+                    "⚠️  Warning: Skipping schedule rule {}, expected HH:MM",
+                    rule.key()
+                );
+                None
+            }
+        })
+        .filter(|(rule, _)| rule.runs_on(date.weekday()))
+        .collect()
+}
+
+/// Rules whose time has passed today but that haven't fired yet
+///
+/// A rule only runs if its time came after watch mode started, or at most
+/// [`GRACE_PERIOD`] before, so starting late in the day doesn't run everything
+/// that was missed at once.
+fn due_rules<'a>(
+    schedule: &'a Schedule,
+    now: DateTime<Tz>,
+    started: DateTime<Tz>,
+    holidays: &HashSet<NaiveDate>,
+    state: &DayState,
+) -> Vec<&'a ScheduleRule> {
+    let today = now.date_naive();
+    rules_on(schedule, today, holidays)
+        .into_iter()
+        .filter(|(rule, time)| {
+            let at = today.and_time(*time);
+            now.time() >= *time
+                && (at >= started.naive_local() || now.naive_local() - at <= GRACE_PERIOD)
+                && !state.fired.contains(&rule.key())
+        })
+        .map(|(rule, _)| rule)
+        .collect()
+}
+
+/// What the scheduler wants from the current watch cycle
+#[derive(Debug)]
+pub struct Plan {
+    /// Rules to run now
+    pub due: Vec<ScheduleRule>,
+    /// Whether Slack may be posted to, which before a scheduled post it may not
+    pub posting: bool,
+}
+
+/// Decide what to do in the current cycle of a watch that started at `started`
+pub fn plan(config: &Config, date: &str, started: DateTime<Tz>) -> Plan {
+    let Some(schedule) = &config.schedule else {
+        return Plan {
+            due: Vec::new(),
+            posting: true,
+        };
+    };
+
+    let now = local_now(config);
+    let holidays = load_holidays(schedule);
+    let state = DayState::load(config, date);
+    let due: Vec<ScheduleRule> = due_rules(schedule, now, started, &holidays, &state)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    // Posting starts at the post rule's time, even if the rule itself was missed
+    let posting = !schedule.has(ScheduledAction::Post)
+        || rules_on(schedule, now.date_naive(), &holidays)
+            .iter()
+            .any(|(rule, time)| rule.action == ScheduledAction::Post && now.time() >= *time);
+
+    Plan { due, posting }
+}
+
+/// Time of the first summary rule on a day, if one runs that day
+pub fn summary_time(schedule: &Schedule, date: NaiveDate) -> Option<NaiveTime> {
+    rules_on(schedule, date, &load_holidays(schedule))
+        .into_iter()
+        .filter(|(rule, _)| rule.action == ScheduledAction::Summary)
+        .map(|(_, time)| time)
+        .min()
+}
+
+/// Remember that a rule fired today, so restarts don't run it again
+pub fn mark_fired(config: &Config, date: &str, rule: &ScheduleRule) {
+    let mut state = DayState::load(config, date);
+    state.fired.insert(rule.key());
+    if let Err(e) = state.save(config, date) {
+        eprintln!(
+            "⚠️  Warning: Could not save scheduled run {}: {}",
+            rule.key(),
+            e
+        );
+    }
+}
+
+/// How long until the next rule today, if any is left
+pub fn until_next_run(config: &Config) -> Option<Duration> {
+    let schedule = config.schedule.as_ref()?;
+    let now = local_now(config);
+    rules_on(schedule, now.date_naive(), &load_holidays(schedule))
+        .into_iter()
+        .filter_map(|(_, time)| {
+            let at = timezone(config)
+                .from_local_datetime(&now.date_naive().and_time(time))
+                .earliest()?;
+            (at > now).then(|| (at - now).to_std().ok()).flatten()
+        })
+        .min()
+}
+
+/// End-of-day message listing what got done
+fn build_summary_message(date: &str, todos: &[(String, bool)]) -> SlackMessage {
+    let done: Vec<&str> = todos
+        .iter()
+        .filter(|(_, done)| *done)
+        .map(|(content, _)| content.as_str())
+        .collect();
+    let open = todos.len() - done.len();
+
+    let mut text = format!(
+        "🏁 *End of day - {}*\n\n{} completed, {} still open\n",
+        date,
+        done.len(),
+        open
+    );
+    for content in done {
+        text.push_str(&format!("\n- [x] {}", content));
+    }
+    SlackMessage::new(text)
+}
+
+/// Post the end-of-day summary in the thread of each of the day's messages
+pub async fn post_summary(config: &Config, date: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let file_path = get_todos_directory(config).join(format!("{}.md", date));
+    let (todos, _) = parse_existing_markdown(&fs::read_to_string(&file_path)?);
+    let message = build_summary_message(date, &todos);
+
+    if let Some(webhook_url) = config
+        .slack_webhook_url
+        .as_deref()
+        .filter(|url| !url.is_empty())
+    {
+        let client = WebhookClient::new(webhook_url)?
+            .with_icons(config.slack_icons.clone().unwrap_or_default())
+            .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default());
+        client.send(&message).await?;
+        println!("🏁 Posted the end-of-day summary through the webhook");
+        return Ok(());
+    }
+
+    let state = DayState::load(config, date);
+    destinations::reply_to_all(config, &state.messages, &message).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: ScheduledAction, time: &str, days: Option<&[&str]>) -> ScheduleRule {
+        ScheduleRule {
+            action,
+            time: time.to_string(),
+            days: days.map(|days| days.iter().map(|day| day.to_string()).collect()),
+        }
+    }
+
+    fn schedule() -> Schedule {
+        Schedule {
+            rules: vec![
+                rule(ScheduledAction::Post, "09:00", Some(&["mon-fri"])),
+                rule(ScheduledAction::Summary, "17:30", Some(&["mon-fri"])),
+            ],
+            holidays_file: None,
+        }
+    }
+
+    fn at(date: &str, time: &str) -> DateTime<Tz> {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        let time = NaiveTime::parse_from_str(time, "%H:%M").unwrap();
+        chrono_tz::Europe::Amsterdam
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .unwrap()
+    }
+
+    fn keys(rules: Vec<&ScheduleRule>) -> Vec<String> {
+        rules.into_iter().map(ScheduleRule::key).collect()
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("mon"), Ok(vec![Weekday::Mon]));
+        assert_eq!(parse_days("fri-mon").unwrap().len(), 4);
+        assert_eq!(parse_days("mon-fri").unwrap().len(), 5);
+        assert!(parse_days("someday").is_err());
+    }
+
+    #[test]
+    fn test_parse_holidays() {
+        let holidays =
+            parse_holidays("# Holidays\n2025-12-25 # Christmas\n\nnot a date\n2025-12-26\n");
+        assert_eq!(holidays.len(), 2);
+        assert!(holidays.contains(&NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()));
+    }
+
+    #[test]
+    fn test_due_rules() {
+        let schedule = schedule();
+        let none = HashSet::new();
+        let mut state = DayState::default();
+        // Watching since before Monday 2025-07-07
+        let started = at("2025-07-06", "20:00");
+        let due = |now: &str, holidays: &HashSet<NaiveDate>, state: &DayState| {
+            keys(due_rules(
+                &schedule,
+                at("2025-07-07", now),
+                started,
+                holidays,
+                state,
+            ))
+        };
+
+        assert!(due("08:59", &none, &state).is_empty());
+        assert_eq!(due("09:00", &none, &state), ["post@09:00"]);
+        assert_eq!(due("18:00", &none, &state), ["post@09:00", "summary@17:30"]);
+
+        // Fired rules don't run again
+        state.fired.insert("post@09:00".to_string());
+        assert_eq!(due("18:00", &none, &state), ["summary@17:30"]);
+
+        // Weekends and holidays
+        assert!(due_rules(&schedule, at("2025-07-05", "18:00"), started, &none, &state).is_empty());
+        let holidays = HashSet::from([NaiveDate::from_ymd_opt(2025, 7, 7).unwrap()]);
+        assert!(due("18:00", &holidays, &state).is_empty());
+    }
+
+    #[test]
+    fn test_due_rules_after_starting_late() {
+        let schedule = schedule();
+        let none = HashSet::new();
+        let state = DayState::default();
+
+        // Rules missed before starting don't run...
+        let started = at("2025-07-07", "18:00");
+        assert!(due_rules(&schedule, at("2025-07-07", "18:00"), started, &none, &state).is_empty());
+
+        // ...unless they were missed by less than the grace period
+        let started = at("2025-07-07", "17:40");
+        assert_eq!(
+            keys(due_rules(
+                &schedule,
+                at("2025-07-07", "17:41"),
+                started,
+                &none,
+                &state
+            )),
+            ["summary@17:30"]
+        );
+
+        // Rules after the start run, however long ago that was
+        assert_eq!(
+            keys(due_rules(
+                &schedule,
+                at("2025-07-07", "22:00"),
+                at("2025-07-07", "17:00"),
+                &none,
+                &state
+            )),
+            ["summary@17:30"]
+        );
+    }
+
+    #[test]
+    fn test_summary_time() {
+        let schedule = schedule();
+        assert_eq!(
+            summary_time(&schedule, NaiveDate::from_ymd_opt(2025, 7, 7).unwrap()),
+            NaiveTime::from_hms_opt(17, 30, 0)
+        );
+        assert_eq!(
+            summary_time(&schedule, NaiveDate::from_ymd_opt(2025, 7, 5).unwrap()),
+            None
+        );
+        assert!(schedule.has(ScheduledAction::Summary));
+    }
+
+    #[test]
+    fn test_build_summary_message() {
+        let todos = vec![("Task A".to_string(), true), ("Task B".to_string(), false)];
+        let message = build_summary_message("2025-07-07", &todos);
+        assert_eq!(
+            message.text,
+            "🏁 *End of day - 2025-07-07*\n\n1 completed, 1 still open\n\n- [x] Task A"
+        );
+    }
+}
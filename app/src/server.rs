@@ -0,0 +1,293 @@
+use crate::{Config, capture, in_background, slash, sync_and_post};
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use slack::{EventPayload, InteractionPayload, SlackError};
+use std::sync::Arc;
+use todoist::TodoistClient;
+
+#[derive(Clone)]
+struct AppState {
+    config: Arc<Config>,
+}
+
+/// Run the HTTP server that receives Slack interactivity, slash command and event requests
+pub async fn serve(config: Config) -> Result<(), Box<dyn std::error::Error>> {
+    if config
+        .slack_signing_secret
+        .as_deref()
+        .is_none_or(str::is_empty)
+    {
+        return Err("slack_signing_secret is required in config.toml to run the server".into());
+    }
+
+    let address = config
+        .server_address
+        .clone()
+        .unwrap_or_else(|| "127.0.0.1:3000".to_string());
+
+    let listener = tokio::net::TcpListener::bind(&address).await?;
+    println!("🌐 Listening for Slack requests on http://{}", address);
+    println!("   Interactivity URL: /slack/interactivity");
+    println!("   Slash command URL: /slack/commands");
+    println!("   Events URL: /slack/events");
+
+    axum::serve(listener, router(config)).await?;
+    Ok(())
+}
+
+fn router(config: Config) -> Router {
+    Router::new()
+        .route("/slack/interactivity", post(interactivity))
+        .route("/slack/commands", post(slash_command))
+        .route("/slack/events", post(events))
+        .with_state(AppState {
+            config: Arc::new(config),
+        })
+}
+
+/// Check the `X-Slack-Signature` and `X-Slack-Request-Timestamp` headers of a request
+fn verify_request(config: &Config, headers: &HeaderMap, body: &[u8]) -> Result<(), SlackError> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| SlackError::SignatureError(format!("Missing {} header", name)))
+    };
+
+    let signing_secret = config.slack_signing_secret.as_deref().unwrap_or_default();
+    slack::verify_signature(
+        signing_secret,
+        header("x-slack-request-timestamp")?,
+        body,
+        header("x-slack-signature")?,
+    )
+}
+
+async fn interactivity(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if let Err(e) = verify_request(&state.config, &headers, &body) {
+        eprintln!("⚠️  Rejected Slack request: {}", e);
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let payload = match slack::parse_interaction_payload(&String::from_utf8_lossy(&body)) {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("⚠️  Could not parse interaction payload: {}", e);
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    handle_interaction(state.config.clone(), payload);
+    StatusCode::OK
+}
+
+/// Act on an interaction coming from either the HTTP endpoint or Socket Mode
+pub fn handle_interaction(config: Arc<Config>, payload: InteractionPayload) {
+    let InteractionPayload::BlockActions(actions) = payload else {
+        return;
+    };
+
+    let task_ids = actions.completed_task_ids();
+    if task_ids.is_empty() {
+        return;
+    }
+
+    in_background(async move {
+        complete_tasks(&config, &task_ids).await;
+    });
+}
+
+/// Close the given tasks in Todoist and re-render today's file and Slack message
+async fn complete_tasks(config: &Config, task_ids: &[String]) {
+    let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+
+    for task_id in task_ids {
+        match client.close_task(task_id).await {
+            Ok(()) => println!("✅ Completed task {} from Slack", task_id),
+            Err(e) => eprintln!("❌ Error completing task {}: {}", task_id, e),
+        }
+    }
+
+    if let Err(e) = sync_and_post(config).await {
+        eprintln!("❌ Error syncing and posting: {}", e);
+    }
+}
+
+async fn slash_command(State(state): State<AppState>, headers: HeaderMap, body: Bytes) -> Response {
+    if let Err(e) = verify_request(&state.config, &headers, &body) {
+        eprintln!("⚠️  Rejected Slack request: {}", e);
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    match slack::parse_slash_command(&String::from_utf8_lossy(&body)) {
+        Ok(command) => Json(slash::handle(state.config.clone(), &command).await).into_response(),
+        Err(e) => {
+            eprintln!("⚠️  Could not parse slash command: {}", e);
+            StatusCode::BAD_REQUEST.into_response()
+        }
+    }
+}
+
+async fn events(State(state): State<AppState>, headers: HeaderMap, body: Bytes) -> Response {
+    if let Err(e) = verify_request(&state.config, &headers, &body) {
+        eprintln!("⚠️  Rejected Slack request: {}", e);
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    match slack::parse_event_payload(&String::from_utf8_lossy(&body)) {
+        Ok(EventPayload::UrlVerification { challenge }) => {
+            Json(serde_json::json!({ "challenge": challenge })).into_response()
+        }
+        Ok(EventPayload::EventCallback(callback)) => {
+            capture::handle_event(state.config.clone(), callback);
+            StatusCode::OK.into_response()
+        }
+        Ok(EventPayload::Unsupported) => StatusCode::OK.into_response(),
+        Err(e) => {
+            eprintln!("⚠️  Could not parse event payload: {}", e);
+            StatusCode::BAD_REQUEST.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test-signing-secret";
+
+    fn test_config() -> Config {
+        Config {
+            todoist_api_token: "test".to_string(),
+            slack_bot_token: "test".to_string(),
+            slack_signing_secret: Some(SECRET.to_string()),
+            ..Config::default()
+        }
+    }
+
+    fn now() -> String {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string()
+    }
+
+    fn signed_request(uri: &str, body: &str, timestamp: &str, secret: &str) -> Request<Body> {
+        Request::post(uri)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .header("x-slack-request-timestamp", timestamp)
+            .header(
+                "x-slack-signature",
+                slack::compute_signature(secret, timestamp, body.as_bytes()),
+            )
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn interaction_body(action_id: &str) -> String {
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123" },
+            "actions": [{ "action_id": action_id, "selected_options": [] }]
+        });
+        serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_interactivity_accepts_signed_request() {
+        let body = interaction_body("complete_task");
+        let request = signed_request("/slack/interactivity", &body, &now(), SECRET);
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_interactivity_rejects_bad_signature() {
+        let body = interaction_body("complete_task");
+        let request = signed_request("/slack/interactivity", &body, &now(), "wrong-secret");
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    fn command_body(text: &str) -> String {
+        serde_urlencoded::to_string([
+            ("command", "/slaist"),
+            ("text", text),
+            ("user_id", "U123"),
+            ("channel_id", "C123"),
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_slash_command_answers_signed_request() {
+        let body = command_body("help");
+        let request = signed_request("/slack/commands", &body, &now(), SECRET);
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["response_type"], "ephemeral");
+        assert!(json["text"].as_str().unwrap().contains("/slaist today"));
+    }
+
+    #[tokio::test]
+    async fn test_slash_command_rejects_bad_signature() {
+        let body = command_body("post");
+        let request = signed_request("/slack/commands", &body, &now(), "wrong-secret");
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_events_answers_url_verification() {
+        let body = r#"{"type":"url_verification","token":"abc","challenge":"challenge-123"}"#;
+        let request = signed_request("/slack/events", body, &now(), SECRET);
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["challenge"], "challenge-123");
+    }
+
+    #[tokio::test]
+    async fn test_events_rejects_bad_signature() {
+        let body = r#"{"type":"url_verification","token":"abc","challenge":"challenge-123"}"#;
+        let request = signed_request("/slack/events", body, &now(), "wrong-secret");
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_interactivity_rejects_replayed_request() {
+        let body = interaction_body("complete_task");
+        let request = signed_request("/slack/interactivity", &body, "1531420618", SECRET);
+
+        let response = router(test_config()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
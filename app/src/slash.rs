@@ -0,0 +1,274 @@
+use crate::render::{self, DailyView, MarkdownStyle};
+use crate::state::DayState;
+use crate::{
+    Config, current_date, destinations, get_todos_directory, in_background, parse_todo_lines,
+    spawn_sync_and_post, try_sync_todos,
+};
+use slack::{CommandResponse, SlashCommand};
+use std::fs;
+use std::sync::Arc;
+use todoist::{NewTask, TodoistClient};
+
+const USAGE: &str = "*Usage:*\n\
+    • `/slaist today` - show today's todos\n\
+    • `/slaist add <text>` - add a todo for today\n\
+    • `/slaist done <n>` - complete the n-th open todo from `/slaist today`\n\
+    • `/slaist post` - post or update today's message in the channel";
+
+/// A parsed `/slaist` subcommand
+#[derive(Debug, PartialEq)]
+enum SlashAction {
+    Today,
+    Add(String),
+    Done(usize),
+    Post,
+    Help,
+}
+
+impl SlashAction {
+    fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (subcommand, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let rest = rest.trim();
+
+        match subcommand {
+            "today" => Ok(SlashAction::Today),
+            "post" => Ok(SlashAction::Post),
+            "" | "help" => Ok(SlashAction::Help),
+            "add" if rest.is_empty() => Err("Usage: `/slaist add <text>`".to_string()),
+            "add" => Ok(SlashAction::Add(rest.to_string())),
+            "done" => match rest.parse::<usize>() {
+                Ok(n) if n > 0 => Ok(SlashAction::Done(n)),
+                _ => Err(
+                    "Usage: `/slaist done <n>`, where n is the number shown by `/slaist today`"
+                        .to_string(),
+                ),
+            },
+            other => Err(format!(
+                "Unknown command `{}`\n\n{}",
+                slack::escape(other),
+                USAGE
+            )),
+        }
+    }
+}
+
+/// Handle a `/slaist` slash command and build the response shown to the user
+pub async fn handle(config: Arc<Config>, command: &SlashCommand) -> CommandResponse {
+    let action = match SlashAction::parse(&command.text) {
+        Ok(action) => action,
+        Err(message) => return CommandResponse::ephemeral(message),
+    };
+
+    match action {
+        SlashAction::Help => CommandResponse::ephemeral(USAGE),
+        SlashAction::Today => today(config).await,
+        SlashAction::Add(text) => add(config, text),
+        SlashAction::Done(n) => done(config, n),
+        SlashAction::Post => {
+            let channel = destinations::configured(&config)
+                .iter()
+                .map(|destination| {
+                    destination
+                        .channel
+                        .clone()
+                        .or_else(|| {
+                            destination
+                                .user
+                                .as_ref()
+                                .map(|user| format!("a DM with {}", user))
+                        })
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            spawn_sync_and_post(config);
+            CommandResponse::in_channel(format!(
+                "📅 <@{}> is posting today's todos to {}",
+                command.user_id, channel
+            ))
+        }
+    }
+}
+
+async fn today(config: Arc<Config>) -> CommandResponse {
+    // Answer from the last sync and sync for the next time
+    let response = match load_view(&config, &MarkdownStyle::cached(&config)) {
+        Ok(view) => CommandResponse::ephemeral(format!(
+            "📅 *Daily Todos - {}*\n\n{}",
+            view.date,
+            sanitize(&config, &number_open_todos(&view))
+        )),
+        Err(e) => CommandResponse::ephemeral(format!(
+            "❌ Could not read today's todos, they are being synced now: {}",
+            e
+        )),
+    };
+    in_background(async move {
+        try_sync_todos(&config, &MarkdownStyle::load(&config).await).await;
+    });
+    response
+}
+
+fn add(config: Arc<Config>, text: String) -> CommandResponse {
+    let content = sanitize(&config, &text);
+    in_background(async move {
+        let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+        let task = NewTask {
+            content: text,
+            due_string: Some("today".to_string()),
+            ..NewTask::default()
+        };
+        match client.add_task(&task).await {
+            Ok(_) => spawn_sync_and_post(config),
+            Err(e) => eprintln!("❌ Error adding todo from Slack: {}", e),
+        }
+    });
+    CommandResponse::ephemeral(format!("➕ Adding: {}", content))
+}
+
+fn done(config: Arc<Config>, n: usize) -> CommandResponse {
+    // Resolve the number against the same list `/slaist today` showed
+    let view = match load_view(&config, &MarkdownStyle::cached(&config)) {
+        Ok(view) => view,
+        Err(e) => {
+            return CommandResponse::ephemeral(format!("❌ Could not read today's todos: {}", e));
+        }
+    };
+    let Some(task) = view.tasks.get(n - 1) else {
+        return CommandResponse::ephemeral(format!("❌ There is no open todo number {}", n));
+    };
+
+    let content = sanitize(&config, &task.content);
+    let task_id = task.id.clone();
+    in_background(async move {
+        let client = TodoistClient::new(config.todoist_api_token.clone(), config.filter.clone());
+        match client.close_task(&task_id).await {
+            Ok(()) => spawn_sync_and_post(config),
+            Err(e) => eprintln!("❌ Error completing task {}: {}", task_id, e),
+        }
+    });
+    CommandResponse::ephemeral(format!("✅ Completing: {}", content))
+}
+
+/// Today's todos as of the last sync, laid out like the daily file
+fn load_view(config: &Config, style: &MarkdownStyle) -> Result<DailyView, String> {
+    let date_str = current_date(config).format("%Y-%m-%d").to_string();
+    let state = DayState::load(config, &date_str);
+    let file_path = get_todos_directory(config).join(format!("{}.md", date_str));
+    let content = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+    let (existing_todos, notes) = parse_todo_lines(&content);
+    Ok(render::daily_view(
+        &state.todos,
+        &existing_todos,
+        notes.as_deref(),
+        style,
+    ))
+}
+
+/// Make task content safe to show in a response, keeping the allowed mentions
+fn sanitize(config: &Config, text: &str) -> String {
+    slack::sanitize(
+        text,
+        config.slack_allowed_mentions.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Number the open todos so they can be referenced with `/slaist done <n>`
+///
+/// The numbers follow [`DailyView::tasks`], which `done` resolves them against.
+fn number_open_todos(view: &DailyView) -> String {
+    let mut lines = Vec::new();
+    let mut number = 0;
+    for group in &view.groups {
+        if let Some(name) = &group.name {
+            lines.push(format!("*{}*", name));
+        }
+        for task in &group.tasks {
+            number += 1;
+            lines.push(format!(
+                "{}*{}.* {}",
+                "    ".repeat(task.depth),
+                number,
+                task.content
+            ));
+        }
+    }
+    if !view.completed.is_empty() {
+        lines.push(String::new());
+        lines.push("*Completed*".to_string());
+        lines.extend(
+            view.completed
+                .iter()
+                .map(|todo| format!("- [x] {}", todo.content)),
+        );
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{MarkdownOptions, SortBy};
+    use crate::test_support;
+    use todoist::Todo;
+
+    #[test]
+    fn test_parse_slash_actions() {
+        assert_eq!(SlashAction::parse("today"), Ok(SlashAction::Today));
+        assert_eq!(SlashAction::parse("  post "), Ok(SlashAction::Post));
+        assert_eq!(SlashAction::parse(""), Ok(SlashAction::Help));
+        assert_eq!(
+            SlashAction::parse("add Write the   release notes"),
+            Ok(SlashAction::Add("Write the   release notes".to_string()))
+        );
+        assert_eq!(SlashAction::parse("done 3"), Ok(SlashAction::Done(3)));
+    }
+
+    #[test]
+    fn test_parse_invalid_slash_actions() {
+        assert!(SlashAction::parse("add").is_err());
+        assert!(SlashAction::parse("done").is_err());
+        assert!(SlashAction::parse("done 0").is_err());
+        assert!(SlashAction::parse("done two").is_err());
+        assert!(
+            SlashAction::parse("dance")
+                .unwrap_err()
+                .contains("Unknown command")
+        );
+        assert!(
+            SlashAction::parse("<!channel>")
+                .unwrap_err()
+                .contains("`&lt;!channel&gt;`")
+        );
+    }
+
+    fn todo(id: &str, priority: u8, checked: bool) -> Todo {
+        Todo {
+            priority,
+            checked,
+            ..test_support::todo(id, &format!("Task {}", id))
+        }
+    }
+
+    #[test]
+    fn test_number_open_todos() {
+        // Sorted by priority, the API's last todo comes first
+        let todos = vec![todo("1", 1, false), todo("2", 4, false), todo("3", 1, true)];
+        let style = MarkdownStyle {
+            options: MarkdownOptions {
+                sort_by: Some(SortBy::Priority),
+                ..MarkdownOptions::default()
+            },
+            ..MarkdownStyle::plain()
+        };
+        let view = render::daily_view(&todos, &[], None, &style);
+
+        assert_eq!(
+            number_open_todos(&view),
+            "*1.* Task 2\n*2.* Task 1\n\n*Completed*\n- [x] Task 3"
+        );
+        let ids: Vec<&str> = view.tasks.iter().map(|task| task.id.as_str()).collect();
+        assert_eq!(ids, ["2", "1"]);
+    }
+}
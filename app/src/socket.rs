@@ -0,0 +1,44 @@
+use crate::{Config, capture, server, slash};
+use slack::{
+    CommandResponse, EventCallback, InteractionPayload, SlashCommand, SocketModeClient,
+    SocketModeHandler,
+};
+use std::sync::Arc;
+
+/// Feeds Socket Mode payloads into the same handlers as the HTTP endpoints
+struct AppHandler {
+    config: Arc<Config>,
+}
+
+impl SocketModeHandler for AppHandler {
+    async fn on_interaction(&self, payload: InteractionPayload) {
+        server::handle_interaction(self.config.clone(), payload);
+    }
+
+    async fn on_slash_command(&self, command: SlashCommand) -> Option<CommandResponse> {
+        Some(slash::handle(self.config.clone(), &command).await)
+    }
+
+    async fn on_event(&self, callback: EventCallback) {
+        capture::handle_event(self.config.clone(), callback);
+    }
+}
+
+/// Receive Slack interactions, slash commands and events over Socket Mode instead of HTTP
+pub async fn run(config: Config) -> Result<(), Box<dyn std::error::Error>> {
+    let app_token = config
+        .slack_app_token
+        .clone()
+        .filter(|token| !token.is_empty())
+        .ok_or("slack_app_token is required in config.toml to use Socket Mode")?;
+
+    let client = SocketModeClient::with_app_token(app_token)?;
+    let handler = AppHandler {
+        config: Arc::new(config),
+    };
+
+    println!("🔌 Connecting to Slack over Socket Mode...");
+    client.run(&handler).await?;
+    println!("🔌 Socket Mode was disabled for this app, stopping");
+    Ok(())
+}
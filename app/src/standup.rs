@@ -0,0 +1,204 @@
+use crate::state::DayState;
+use crate::{
+    Config, current_date, destinations, fetch_todos, get_todos_directory, parse_existing_markdown,
+    rollover,
+};
+use serde::{Deserialize, Serialize};
+use slack::SlackMessage;
+use std::error::Error;
+use std::fs;
+use todoist::Todo;
+
+/// How `slaist standup` builds and posts the standup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StandupOptions {
+    /// Label, without the `@`, of open todos that are blocked
+    pub blocked_label: String,
+    /// Lines in the notes section starting with this are blockers too
+    pub notes_prefix: String,
+    /// Reply in the thread of the day's message instead of posting a message of its own
+    pub thread: bool,
+}
+
+impl Default for StandupOptions {
+    fn default() -> Self {
+        Self {
+            blocked_label: "blocked".to_string(),
+            notes_prefix: "Blocked:".to_string(),
+            thread: false,
+        }
+    }
+}
+
+/// The three parts of a standup
+#[derive(Debug, Default, PartialEq)]
+pub struct Standup {
+    /// Todos completed on the previous day
+    pub yesterday: Vec<String>,
+    /// Open todos that aren't blocked
+    pub today: Vec<String>,
+    /// Blocked todos and blockers from the notes
+    pub blockers: Vec<String>,
+}
+
+impl Standup {
+    /// Build the standup from the previous day's file, the current todos and today's notes
+    pub fn build(
+        options: &StandupOptions,
+        previous_markdown: Option<&str>,
+        todos: &[Todo],
+        notes: Option<&str>,
+    ) -> Self {
+        let yesterday = previous_markdown
+            .map(|markdown| {
+                parse_existing_markdown(markdown)
+                    .0
+                    .into_iter()
+                    .filter(|(_, done)| *done)
+                    .map(|(content, _)| content)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (blocked, open): (Vec<&Todo>, Vec<&Todo>) = todos
+            .iter()
+            .filter(|todo| !todo.checked)
+            .partition(|todo| todo.labels.contains(&options.blocked_label));
+
+        let mut blockers: Vec<String> = blocked.iter().map(|todo| todo.content.clone()).collect();
+        if !options.notes_prefix.is_empty() {
+            blockers.extend(notes.into_iter().flat_map(str::lines).filter_map(|line| {
+                let line = line.trim();
+                let line = line.strip_prefix("- ").unwrap_or(line);
+                line.strip_prefix(options.notes_prefix.as_str())
+                    .map(|blocker| blocker.trim().to_string())
+                    .filter(|blocker| !blocker.is_empty())
+            }));
+        }
+
+        Self {
+            yesterday,
+            today: open.iter().map(|todo| todo.content.clone()).collect(),
+            blockers,
+        }
+    }
+
+    /// The standup as Slack markdown
+    pub fn text(&self, date: &str) -> String {
+        let section = |title: &str, items: &[String], empty: &str| {
+            let mut section = format!("*{}*\n", title);
+            if items.is_empty() {
+                section.push_str(&format!("_{}_\n", empty));
+            }
+            for item in items {
+                section.push_str(&format!("• {}\n", item));
+            }
+            section
+        };
+        format!(
+            "🧍 *Standup - {}*\n\n{}\n{}\n{}",
+            date,
+            section("Yesterday", &self.yesterday, "Nothing completed"),
+            section("Today", &self.today, "Nothing planned"),
+            section("Blockers", &self.blockers, "None"),
+        )
+    }
+}
+
+/// Build today's standup and post it to Slack, in the thread of the day's message if `thread` is set
+pub async fn run(config: &Config, thread: bool) -> Result<(), Box<dyn Error>> {
+    let options = config.standup.clone().unwrap_or_default();
+    let thread = thread || options.thread;
+    let date = current_date(config);
+    let date_str = date.format("%Y-%m-%d").to_string();
+    let directory = get_todos_directory(config);
+
+    let todos = fetch_todos(config)
+        .await
+        .ok_or("Could not fetch the todos from Todoist")?;
+    let previous_markdown = rollover::previous_day(config, date).and_then(|previous| {
+        fs::read_to_string(directory.join(format!("{}.md", previous.format("%Y-%m-%d")))).ok()
+    });
+    let notes = fs::read_to_string(directory.join(format!("{}.md", date_str)))
+        .ok()
+        .and_then(|markdown| parse_existing_markdown(&markdown).1);
+
+    let standup = Standup::build(
+        &options,
+        previous_markdown.as_deref(),
+        &todos,
+        notes.as_deref(),
+    );
+    let message = SlackMessage::new(standup.text(&date_str));
+
+    if !thread {
+        return destinations::post_to_all(config, &message).await;
+    }
+    if config
+        .slack_webhook_url
+        .as_deref()
+        .is_some_and(|url| !url.is_empty())
+    {
+        return Err("Incoming webhooks can't reply in threads".into());
+    }
+    let state = DayState::load(config, &date_str);
+    if state.messages.is_empty() {
+        println!("🧍 No message has been posted today to reply to, posting the standup on its own");
+        return destinations::post_to_all(config, &message).await;
+    }
+    destinations::reply_to_all(config, &state.messages, &message).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(content: &str, labels: &[&str], checked: bool) -> Todo {
+        Todo {
+            labels: test_support::labels(labels),
+            checked,
+            ..test_support::todo(content, content)
+        }
+    }
+
+    #[test]
+    fn test_build_standup() {
+        let previous = "## Active Todos\n\n- [ ] Task A *(carried 1 day)*\n\n## Completed Todos\n\n- [x] Task B · 🔴 P1\n- [x] Task C *(marked as finished)*\n---\n- [x] Not a todo\n";
+        let todos = vec![
+            todo("Task A", &[], false),
+            todo("Task D", &["blocked", "work"], false),
+            todo("Task E", &["blocked"], true),
+        ];
+        let notes = "Some notes\n- Blocked: waiting on review\nBlocked:\n";
+
+        let standup = Standup::build(
+            &StandupOptions::default(),
+            Some(previous),
+            &todos,
+            Some(notes),
+        );
+        assert_eq!(
+            standup,
+            Standup {
+                yesterday: vec!["Task B".to_string(), "Task C".to_string()],
+                today: vec!["Task A".to_string()],
+                blockers: vec!["Task D".to_string(), "waiting on review".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_standup_text() {
+        let standup = Standup {
+            yesterday: vec!["Task B".to_string()],
+            today: Vec::new(),
+            blockers: vec!["Task D".to_string()],
+        };
+        assert_eq!(
+            standup.text("2025-07-07"),
+            "🧍 *Standup - 2025-07-07*\n\n*Yesterday*\n• Task B\n\n*Today*\n_Nothing planned_\n\n*Blockers*\n• Task D\n"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use crate::destinations::{self, PostedMessages};
+use crate::webhook::SentTodos;
+use crate::{Config, get_todos_directory, validate_message_id};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use slack::PostedMessage;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use todoist::Todo;
+
+/// Directory inside the todos directory that holds the state files
+const STATE_DIRECTORY: &str = ".state";
+/// File in the state directory with the Todoist timezone of the last lookup
+const TIMEZONE_FILE: &str = "timezone";
+/// File in the state directory with the project and section names of the last sync
+const NAMES_FILE: &str = "names.json";
+
+/// What Slaist remembers about a day, kept next to the markdown file so the
+/// markdown only holds human content
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayState {
+    /// Messages posted to each destination, keyed by [`destinations::Destination::key`]
+    #[serde(default)]
+    pub messages: PostedMessages,
+    /// ID of the daily file uploaded with `slack_attach_file`
+    #[serde(default)]
+    pub file_id: Option<String>,
+    /// Hash of the uploaded file's content and the message it's attached to
+    #[serde(default)]
+    pub file_hash: Option<u64>,
+    /// Todoist task ID of each todo in the markdown, by content
+    #[serde(default)]
+    pub task_ids: BTreeMap<String, String>,
+    /// When the day's todos were last synced from Todoist
+    #[serde(default)]
+    pub last_sync: Option<DateTime<Utc>>,
+    /// Open and completed todos announced through the incoming webhook
+    #[serde(default)]
+    pub webhook_sent: Option<SentTodos>,
+    /// Scheduled rules that already ran, by [`crate::schedule::ScheduleRule::key`]
+    #[serde(default)]
+    pub fired: BTreeSet<String>,
+    /// Open todos carried over from earlier days, with the number of days carried
+    #[serde(default)]
+    pub carried: BTreeMap<String, u32>,
+    /// When the day was rolled over and its file and messages got their final update
+    #[serde(default)]
+    pub finalized: Option<DateTime<Utc>>,
+    /// Todos fetched by the last sync, so the Home tab can be published without syncing
+    #[serde(default)]
+    pub todos: Vec<Todo>,
+    /// Project name of each completed todo when it was first seen completed, by Todoist task ID
+    #[serde(default)]
+    pub completed_projects: BTreeMap<String, String>,
+}
+
+fn state_path(config: &Config, date: &str) -> PathBuf {
+    get_todos_directory(config)
+        .join(STATE_DIRECTORY)
+        .join(format!("{}.json", date))
+}
+
+fn markdown_path(config: &Config, date: &str) -> PathBuf {
+    get_todos_directory(config).join(format!("{}.md", date))
+}
+
+impl DayState {
+    /// Load the state of a day, moving metadata comments left in its markdown
+    /// file by earlier versions into it
+    pub fn load(config: &Config, date: &str) -> Self {
+        let path = state_path(config, date);
+        let mut state = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                eprintln!(
+                    "⚠️  Warning: Ignoring unreadable state file {}: {}",
+                    path.display(),
+                    e
+                );
+                DayState::default()
+            }),
+            Err(_) => DayState::default(),
+        };
+
+        let markdown_path = markdown_path(config, date);
+        let Ok(content) = fs::read_to_string(&markdown_path) else {
+            return state;
+        };
+        let (stripped, legacy) = migrate_comments(&content, &destinations::configured(config));
+        if stripped == content {
+            return state;
+        }
+
+        state.merge_legacy(legacy);
+        match state.save(config, date) {
+            Ok(()) => {
+                if let Err(e) = fs::write(&markdown_path, stripped) {
+                    eprintln!(
+                        "⚠️  Warning: Could not remove metadata from {}: {}",
+                        markdown_path.display(),
+                        e
+                    );
+                } else {
+                    println!(
+                        "📦 Moved Slack metadata from {} to {}",
+                        markdown_path.display(),
+                        path.display()
+                    );
+                }
+            }
+            Err(e) => eprintln!("⚠️  Warning: Could not migrate metadata: {}", e),
+        }
+        state
+    }
+
+    /// Write the state of a day
+    pub fn save(&self, config: &Config, date: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = state_path(config, date);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| format!("Could not write state file {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    /// Fill in what the state doesn't know yet from migrated comments
+    fn merge_legacy(&mut self, legacy: DayState) {
+        for (key, message) in legacy.messages {
+            self.messages.entry(key).or_insert(message);
+        }
+    }
+}
+
+fn timezone_path(config: &Config) -> PathBuf {
+    get_todos_directory(config)
+        .join(STATE_DIRECTORY)
+        .join(TIMEZONE_FILE)
+}
+
+/// The timezone saved by [`save_timezone`], unless it was saved longer than `max_age` ago
+pub fn cached_timezone(config: &Config, max_age: Duration) -> Option<Tz> {
+    let path = timezone_path(config);
+    let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+    if age > max_age {
+        return None;
+    }
+    fs::read_to_string(&path).ok()?.trim().parse().ok()
+}
+
+/// Remember the timezone looked up in Todoist, so later runs don't have to
+pub fn save_timezone(config: &Config, timezone: Tz) -> Result<(), Box<dyn std::error::Error>> {
+    let path = timezone_path(config);
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(&path, timezone.name())?;
+    Ok(())
+}
+
+/// Project and section names by ID, as fetched by the last sync that showed them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Names {
+    #[serde(default)]
+    pub projects: HashMap<String, String>,
+    #[serde(default)]
+    pub sections: HashMap<String, String>,
+}
+
+fn names_path(config: &Config) -> PathBuf {
+    get_todos_directory(config)
+        .join(STATE_DIRECTORY)
+        .join(NAMES_FILE)
+}
+
+/// The names saved by [`save_names`], or none if they were never saved
+pub fn cached_names(config: &Config) -> Names {
+    fs::read_to_string(names_path(config))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Remember the names fetched from Todoist, so replies that can't wait for Todoist
+/// can still show them
+pub fn save_names(config: &Config, names: &Names) -> Result<(), Box<dyn std::error::Error>> {
+    let path = names_path(config);
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(names)?)?;
+    Ok(())
+}
+
+/// Split the `<!-- slack_message_id: ... -->` comments earlier versions kept in the
+/// markdown off the content, returning the content without them and the state they
+/// described
+///
+/// The message belongs to the first destination. It is tracked with the channel as
+/// configured, often a name, which is looked up by ID on the next update.
+fn migrate_comments(
+    content: &str,
+    destinations: &[destinations::Destination],
+) -> (String, DayState) {
+    let mut state = DayState::default();
+    let mut lines = Vec::new();
+    let mut migrated = false;
+
+    for line in content.lines() {
+        let Some(ts) = line
+            .strip_prefix("<!-- slack_message_id: ")
+            .and_then(|rest| rest.strip_suffix(" -->"))
+        else {
+            lines.push(line);
+            continue;
+        };
+        migrated = true;
+
+        let first = destinations
+            .first()
+            .filter(|first| first.channel.is_some() && validate_message_id(ts));
+        if let Some(first) = first {
+            state.messages.entry(first.key()).or_insert(PostedMessage {
+                channel: first.channel.clone().unwrap_or_default(),
+                ts: ts.to_string(),
+            });
+        }
+    }
+
+    if !migrated {
+        return (content.to_string(), state);
+    }
+    let mut stripped = lines.join("\n");
+    if content.ends_with('\n') && !stripped.is_empty() {
+        stripped.push('\n');
+    }
+    (stripped, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::destinations::Destination;
+
+    fn channel(name: &str) -> Destination {
+        Destination {
+            channel: Some(name.to_string()),
+            ..Destination::default()
+        }
+    }
+
+    fn config(directory: &std::path::Path) -> Config {
+        Config {
+            todos_directory: Some(directory.to_string_lossy().to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_migrate_comments() {
+        let content =
+            "<!-- slack_message_id: 1234567890.123456 -->\n## Active Todos\n\n- [ ] Task A\n";
+        let (stripped, state) = migrate_comments(content, &[channel("#general")]);
+
+        assert_eq!(stripped, "## Active Todos\n\n- [ ] Task A\n");
+        assert_eq!(
+            state.messages.get("channel:#general"),
+            Some(&PostedMessage {
+                channel: "#general".to_string(),
+                ts: "1234567890.123456".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_invalid_comments() {
+        let content = "<!-- slack_message_id: bogus -->\n## Active Todos";
+        let (stripped, state) = migrate_comments(content, &[channel("#general")]);
+
+        assert_eq!(stripped, "## Active Todos");
+        assert!(state.messages.is_empty());
+
+        let (unchanged, state) = migrate_comments("## Active Todos\n", &[channel("#general")]);
+        assert_eq!(unchanged, "## Active Todos\n");
+        assert_eq!(state, DayState::default());
+    }
+
+    #[test]
+    fn test_load_migrates_markdown_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let directory = directory.path();
+        let config = config(directory);
+        fs::write(
+            directory.join("2025-07-07.md"),
+            "<!-- slack_message_id: 1234567890.123456 -->\n## Active Todos\n",
+        )
+        .unwrap();
+
+        let state = DayState::load(&config, "2025-07-07");
+        assert_eq!(state.messages["channel:#general"].ts, "1234567890.123456");
+        assert_eq!(
+            fs::read_to_string(directory.join("2025-07-07.md")).unwrap(),
+            "## Active Todos\n"
+        );
+
+        // The state now comes from the state file
+        assert_eq!(DayState::load(&config, "2025-07-07"), state);
+    }
+
+    #[test]
+    fn test_cached_timezone() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = config(directory.path());
+        assert_eq!(cached_timezone(&config, Duration::MAX), None);
+
+        save_timezone(&config, chrono_tz::Europe::Amsterdam).unwrap();
+        assert_eq!(
+            cached_timezone(&config, Duration::MAX),
+            Some(chrono_tz::Europe::Amsterdam)
+        );
+        assert_eq!(cached_timezone(&config, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn test_cached_names() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = config(directory.path());
+        assert_eq!(cached_names(&config), Names::default());
+
+        let names = Names {
+            projects: HashMap::from([("p1".to_string(), "Work".to_string())]),
+            sections: HashMap::new(),
+        };
+        save_names(&config, &names).unwrap();
+        assert_eq!(cached_names(&config), names);
+    }
+}
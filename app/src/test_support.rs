@@ -0,0 +1,48 @@
+//! Fixtures shared by the tests of the app's modules
+
+use todoist::{Todo, TodoDue};
+
+/// An open P4 todo in `project1`, to be adjusted with struct update syntax
+pub fn todo(id: &str, content: &str) -> Todo {
+    Todo {
+        id: id.to_string(),
+        user_id: "user1".to_string(),
+        project_id: "project1".to_string(),
+        section_id: None,
+        parent_id: None,
+        content: content.to_string(),
+        description: None,
+        priority: 1,
+        labels: Vec::new(),
+        due: None,
+        deadline: None,
+        duration: None,
+        checked: false,
+        is_deleted: false,
+        added_at: "2025-07-07T00:00:00Z".to_string(),
+        completed_at: None,
+        updated_at: "2025-07-07T00:00:00Z".to_string(),
+        child_order: 1,
+        day_order: None,
+        is_collapsed: None,
+        added_by_uid: None,
+        assigned_by_uid: None,
+        responsible_uid: None,
+    }
+}
+
+/// A due date, with a time if `date` has one
+pub fn due(date: &str) -> TodoDue {
+    TodoDue {
+        date: date.to_string(),
+        is_recurring: false,
+        datetime: None,
+        string: date.to_string(),
+        timezone: None,
+    }
+}
+
+/// Labels from string slices
+pub fn labels(labels: &[&str]) -> Vec<String> {
+    labels.iter().map(|label| label.to_string()).collect()
+}
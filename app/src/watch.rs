@@ -0,0 +1,225 @@
+use crate::render::MarkdownStyle;
+use crate::schedule::{self, ScheduledAction};
+use crate::state::DayState;
+use crate::{
+    Config, current_date, focus, get_todos_directory, home, local_now, post_slack, try_sync_todos,
+    webhook,
+};
+use chrono::DateTime;
+use chrono_tz::Tz;
+use std::collections::hash_map::DefaultHasher;
+use std::error::Error;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+use todoist::Todo;
+use tokio::sync::watch;
+
+/// Seconds between cycles if `watch_interval` isn't configured
+pub const DEFAULT_WATCH_INTERVAL: u64 = 60;
+
+/// Longest wait between cycles while they keep failing, unless the interval is longer
+const MAX_BACKOFF: Duration = Duration::from_secs(15 * 60);
+
+/// Wait before the next cycle: the interval, doubled for every failure in a row
+fn next_delay(interval: Duration, failures: u32) -> Duration {
+    if failures == 0 {
+        return interval;
+    }
+    interval
+        .saturating_mul(2u32.saturating_pow(failures.min(16)))
+        .min(MAX_BACKOFF.max(interval))
+}
+
+/// Everything the Slack message is built from, so unchanged days can skip the update
+fn fingerprint(date: &str, markdown: &str, todos: &[Todo]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    date.hash(&mut hasher);
+    markdown.hash(&mut hasher);
+    for todo in todos {
+        todo.id.hash(&mut hasher);
+        todo.content.hash(&mut hasher);
+        todo.checked.hash(&mut hasher);
+        todo.priority.hash(&mut hasher);
+        todo.labels.hash(&mut hasher);
+        todo.due.as_ref().map(|due| &due.date).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Sync the markdown file and update Slack if anything changed since the last cycle
+async fn cycle(
+    config: &Config,
+    started: DateTime<Tz>,
+    last_fingerprint: &mut Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    let style = MarkdownStyle::load(config).await;
+    let todos = try_sync_todos(config, &style)
+        .await
+        .ok_or("Could not fetch todos from Todoist")?;
+
+    let date_str = current_date(config).format("%Y-%m-%d").to_string();
+    let file_path = get_todos_directory(config).join(format!("{}.md", date_str));
+    let markdown = fs::read_to_string(&file_path).unwrap_or_default();
+    let fingerprint = fingerprint(&date_str, &markdown, &todos);
+    let plan = schedule::plan(config, &date_str, started);
+    let summary_pending = webhook::summary_pending(config, &DayState::load(config, &date_str));
+    if *last_fingerprint == Some(fingerprint) && plan.due.is_empty() && !summary_pending {
+        println!("⏭️  Nothing changed, skipping the Slack update");
+        return Ok(());
+    }
+
+    // Boxed errors aren't Send, so keep only the message while the rest is updated
+    let posted = if plan.posting {
+        post_slack(config, &todos, &style)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        println!("⏰ Not posting to Slack before the scheduled post time");
+        Ok(())
+    };
+    if let Err(e) = focus::sync_status(config, &todos).await {
+        eprintln!("❌ Error updating Slack status: {}", e);
+    }
+    home::refresh(config).await;
+
+    for rule in &plan.due {
+        println!("⏰ Running scheduled {}", rule.key());
+        // Through a webhook with the summary policy, the summary was the message just posted
+        if rule.action == ScheduledAction::Summary && !webhook::posts_summary(config) {
+            schedule::post_summary(config, &date_str).await?;
+        }
+        schedule::mark_fired(config, &date_str, rule);
+    }
+
+    // A destination that failed is retried next cycle, even if nothing changes
+    posted?;
+    *last_fingerprint = Some(fingerprint);
+    Ok(())
+}
+
+/// Resolve once SIGINT (Ctrl+C) or, on Unix, SIGTERM arrives
+async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => eprintln!("⚠️  Warning: Could not listen for SIGTERM: {}", e),
+        }
+    }
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        eprintln!("⚠️  Warning: Could not listen for Ctrl+C: {}", e);
+    }
+}
+
+/// Flip to `true` on the first shutdown signal
+///
+/// The signal handlers are installed right away, so a signal during a cycle
+/// doesn't kill the process but lets the cycle finish.
+fn listen_for_shutdown() -> watch::Receiver<bool> {
+    let (sender, receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        println!("🛑 Stopping after the current cycle...");
+        let _ = sender.send(true);
+    });
+    receiver
+}
+
+/// Sync and post on an interval until SIGINT or SIGTERM
+pub async fn run(config: &Config, interval: Duration) -> Result<(), Box<dyn Error>> {
+    if interval.is_zero() {
+        return Err("The watch interval must be at least one second".into());
+    }
+
+    let mut shutdown = listen_for_shutdown();
+    let started = local_now(config);
+    let mut last_fingerprint = None;
+    let mut failures = 0;
+
+    println!(
+        "👀 Syncing every {} seconds, press Ctrl+C to stop",
+        interval.as_secs()
+    );
+    loop {
+        println!("\n{:-<60}", "");
+        match cycle(config, started, &mut last_fingerprint).await {
+            Ok(()) => failures = 0,
+            Err(e) => {
+                failures += 1;
+                eprintln!("❌ Sync failed: {}", e);
+            }
+        }
+
+        if *shutdown.borrow() {
+            break;
+        }
+        let mut delay = next_delay(interval, failures);
+        if failures > 0 {
+            println!(
+                "⏳ Retrying in {} seconds ({} failures in a row)",
+                delay.as_secs(),
+                failures
+            );
+        } else if let Some(until) = schedule::until_next_run(config) {
+            // Wake up in time for the next scheduled run
+            delay = delay.min(until + Duration::from_secs(1));
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.changed() => break,
+        }
+    }
+
+    println!("👋 Stopped watching");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(id: &str, checked: bool) -> Todo {
+        Todo {
+            checked,
+            ..test_support::todo(id, &format!("Task {}", id))
+        }
+    }
+
+    #[test]
+    fn test_next_delay_backs_off() {
+        let interval = Duration::from_secs(60);
+
+        assert_eq!(next_delay(interval, 0), interval);
+        assert_eq!(next_delay(interval, 1), Duration::from_secs(120));
+        assert_eq!(next_delay(interval, 3), Duration::from_secs(480));
+        assert_eq!(next_delay(interval, 10), MAX_BACKOFF);
+        assert_eq!(next_delay(interval, u32::MAX), MAX_BACKOFF);
+
+        // Intervals longer than the backoff limit are never shortened
+        let hourly = Duration::from_secs(3600);
+        assert_eq!(next_delay(hourly, 2), hourly);
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_content() {
+        let todos = vec![todo("1", false)];
+        let base = fingerprint("2025-07-07", "- [ ] Task 1", &todos);
+
+        assert_eq!(base, fingerprint("2025-07-07", "- [ ] Task 1", &todos));
+        assert_ne!(base, fingerprint("2025-07-08", "- [ ] Task 1", &todos));
+        assert_ne!(base, fingerprint("2025-07-07", "- [x] Task 1", &todos));
+        assert_ne!(
+            base,
+            fingerprint("2025-07-07", "- [ ] Task 1", &[todo("1", true)])
+        );
+    }
+}
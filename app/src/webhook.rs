@@ -0,0 +1,310 @@
+use crate::render::{self, MarkdownStyle};
+use crate::schedule::{self, ScheduledAction};
+use crate::state::DayState;
+use crate::{Config, build_slack_message, current_date, get_todos_directory, local_now};
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use slack::{SlackMessage, SlackTask, WebhookClient};
+use std::collections::BTreeSet;
+use std::fs;
+use todoist::Todo;
+
+/// Time of day the summary is posted with [`WebhookPolicy::Summary`] if none is configured
+const DEFAULT_SUMMARY_TIME: &str = "17:00";
+
+/// When to post through an incoming webhook, which can't update earlier messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookPolicy {
+    /// Post the first sync of the day and nothing after that
+    #[default]
+    Once,
+    /// Post a single summary once `webhook_summary_time` has passed
+    Summary,
+    /// Post the first sync of the day, then only what was added or completed since
+    Delta,
+}
+
+/// Todos included in the webhook posts so far today
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SentTodos {
+    pub open: BTreeSet<String>,
+    /// Completions already announced
+    pub done: BTreeSet<String>,
+}
+
+impl SentTodos {
+    fn from_todos(todos: &[Todo]) -> Self {
+        let mut sent = SentTodos::default();
+        for todo in todos {
+            if todo.checked {
+                sent.done.insert(todo.id.clone());
+            } else {
+                sent.open.insert(todo.id.clone());
+            }
+        }
+        sent
+    }
+}
+
+/// What to post for the current sync
+#[derive(Debug)]
+enum Delivery<'a> {
+    Full,
+    Delta {
+        added: Vec<&'a Todo>,
+        completed: Vec<&'a Todo>,
+    },
+}
+
+/// Decide what, if anything, to post for this sync
+fn plan_delivery<'a>(
+    policy: WebhookPolicy,
+    sent: Option<&SentTodos>,
+    todos: &'a [Todo],
+    summary_due: bool,
+) -> Option<Delivery<'a>> {
+    match (policy, sent) {
+        (WebhookPolicy::Once | WebhookPolicy::Delta, None) => Some(Delivery::Full),
+        (WebhookPolicy::Summary, None) if summary_due => Some(Delivery::Full),
+        (WebhookPolicy::Delta, Some(sent)) => {
+            let added: Vec<&Todo> = todos
+                .iter()
+                .filter(|todo| !todo.checked)
+                .filter(|todo| !sent.open.contains(&todo.id) && !sent.done.contains(&todo.id))
+                .collect();
+            let completed: Vec<&Todo> = todos
+                .iter()
+                .filter(|todo| todo.checked && !sent.done.contains(&todo.id))
+                .collect();
+
+            if added.is_empty() && completed.is_empty() {
+                None
+            } else {
+                Some(Delivery::Delta { added, completed })
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Message listing only what changed since the last webhook post
+fn build_delta_message(date: &str, added: &[&Todo], completed: &[&Todo]) -> SlackMessage {
+    let mut text = format!("📅 *Daily Todos - {} (update)*\n", date);
+
+    if !completed.is_empty() {
+        text.push_str("\n*Completed:*\n");
+        for todo in completed {
+            text.push_str(&format!("- [x] {}\n", todo.content));
+        }
+    }
+    if !added.is_empty() {
+        text.push_str("\n*New:*\n");
+        for todo in added {
+            text.push_str(&format!("- [ ] {}\n", todo.content));
+        }
+    }
+
+    let tasks = added
+        .iter()
+        .map(|todo| SlackTask {
+            id: todo.id.clone(),
+            content: todo.content.clone(),
+            priority: todo.priority,
+            labels: todo.labels.clone(),
+            ..SlackTask::default()
+        })
+        .collect();
+    SlackMessage::new(text).with_tasks(tasks)
+}
+
+/// Whether the summary time has passed today
+///
+/// With summary rules in the schedule, the summary is posted by those instead of
+/// at `webhook_summary_time`, so there's a single summary time to configure.
+fn summary_due(config: &Config) -> bool {
+    let now = local_now(config);
+    if let Some(schedule) = config
+        .schedule
+        .as_ref()
+        .filter(|schedule| schedule.has(ScheduledAction::Summary))
+    {
+        return schedule::summary_time(schedule, now.date_naive())
+            .is_some_and(|time| now.time() >= time);
+    }
+
+    let summary_time = config
+        .webhook_summary_time
+        .as_deref()
+        .unwrap_or(DEFAULT_SUMMARY_TIME);
+    match NaiveTime::parse_from_str(summary_time, "%H:%M") {
+        Ok(time) => now.time() >= time,
+        Err(_) => {
+            eprintln!(
+                "⚠️  Warning: Invalid webhook_summary_time {:?}, expected HH:MM",
+                summary_time
+            );
+            false
+        }
+    }
+}
+
+/// Whether posts go through the webhook with [`WebhookPolicy::Summary`], in which
+/// case the day's only message is the summary
+pub fn posts_summary(config: &Config) -> bool {
+    config
+        .slack_webhook_url
+        .as_deref()
+        .is_some_and(|url| !url.is_empty())
+        && config.webhook_policy.unwrap_or_default() == WebhookPolicy::Summary
+}
+
+/// Whether the summary of [`WebhookPolicy::Summary`] is due but hasn't been posted yet
+pub fn summary_pending(config: &Config, state: &DayState) -> bool {
+    posts_summary(config) && state.webhook_sent.is_none() && summary_due(config)
+}
+
+/// Post today's todos through the incoming webhook, following the configured policy
+pub async fn post(
+    config: &Config,
+    todos: &[Todo],
+    webhook_url: &str,
+    style: &MarkdownStyle,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let date_str = current_date(config).format("%Y-%m-%d").to_string();
+    let mut state = DayState::load(config, &date_str);
+    let file_path = get_todos_directory(config).join(format!("{}.md", date_str));
+    let markdown_content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Could not read {}: {}", file_path.display(), e))?;
+
+    let policy = config.webhook_policy.unwrap_or_default();
+    let Some(delivery) = plan_delivery(
+        policy,
+        state.webhook_sent.as_ref(),
+        todos,
+        summary_due(config),
+    ) else {
+        println!(
+            "⏭️  Nothing to post through the webhook ({:?} policy)",
+            policy
+        );
+        return Ok(());
+    };
+
+    let message = match &delivery {
+        Delivery::Full => {
+            let text =
+                render::slack_text(config, style, &date_str, &markdown_content, todos, false);
+            build_slack_message(config, &date_str, text, todos)
+        }
+        Delivery::Delta { added, completed } => build_delta_message(&date_str, added, completed),
+    };
+
+    let client = WebhookClient::new(webhook_url)?
+        .with_icons(config.slack_icons.clone().unwrap_or_default())
+        .with_allowed_mentions(config.slack_allowed_mentions.clone().unwrap_or_default());
+    println!("📢 Posting through incoming webhook...");
+    client.send(&message).await?;
+    println!("✅ Successfully posted today's todos through the webhook!");
+
+    state.webhook_sent = Some(SentTodos::from_todos(todos));
+    if let Err(e) = state.save(config, &date_str) {
+        eprintln!("⚠️  Warning: Could not save webhook state: {}", e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn todo(id: &str, checked: bool) -> Todo {
+        Todo {
+            checked,
+            ..test_support::todo(id, &format!("Task {}", id))
+        }
+    }
+
+    #[test]
+    fn test_once_policy_posts_first_sync_only() {
+        let todos = vec![todo("1", false)];
+        let sent = SentTodos::from_todos(&todos);
+
+        assert!(matches!(
+            plan_delivery(WebhookPolicy::Once, None, &todos, false),
+            Some(Delivery::Full)
+        ));
+        assert!(plan_delivery(WebhookPolicy::Once, Some(&sent), &todos, true).is_none());
+    }
+
+    #[test]
+    fn test_summary_policy_waits_for_summary_time() {
+        let todos = vec![todo("1", false)];
+
+        assert!(plan_delivery(WebhookPolicy::Summary, None, &todos, false).is_none());
+        assert!(matches!(
+            plan_delivery(WebhookPolicy::Summary, None, &todos, true),
+            Some(Delivery::Full)
+        ));
+        assert!(
+            plan_delivery(
+                WebhookPolicy::Summary,
+                Some(&SentTodos::from_todos(&todos)),
+                &todos,
+                true
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_summary_pending() {
+        let config = Config {
+            slack_webhook_url: Some("https://hooks.slack.com/services/T/B/X".to_string()),
+            webhook_policy: Some(WebhookPolicy::Summary),
+            webhook_summary_time: Some("00:00".to_string()),
+            ..Config::default()
+        };
+        let mut state = DayState::default();
+        assert!(summary_pending(&config, &state));
+
+        state.webhook_sent = Some(SentTodos::default());
+        assert!(!summary_pending(&config, &state));
+
+        let once = Config {
+            webhook_policy: Some(WebhookPolicy::Once),
+            ..config
+        };
+        assert!(!summary_pending(&once, &DayState::default()));
+    }
+
+    #[test]
+    fn test_delta_policy_posts_changes() {
+        let before = vec![todo("1", false), todo("2", false)];
+        let sent = SentTodos::from_todos(&before);
+        let after = vec![todo("1", true), todo("2", false), todo("3", false)];
+
+        assert!(matches!(
+            plan_delivery(WebhookPolicy::Delta, None, &after, false),
+            Some(Delivery::Full)
+        ));
+        assert!(plan_delivery(WebhookPolicy::Delta, Some(&sent), &before, false).is_none());
+
+        let Some(Delivery::Delta { added, completed }) =
+            plan_delivery(WebhookPolicy::Delta, Some(&sent), &after, false)
+        else {
+            panic!("Expected a delta");
+        };
+        assert_eq!(added.iter().map(|t| &t.id).collect::<Vec<_>>(), ["3"]);
+        assert_eq!(completed.iter().map(|t| &t.id).collect::<Vec<_>>(), ["1"]);
+
+        let message = build_delta_message("2025-07-07", &added, &completed);
+        assert_eq!(
+            message.text,
+            "📅 *Daily Todos - 2025-07-07 (update)*\n\n*Completed:*\n- [x] Task 1\n\n*New:*\n- [ ] Task 3\n"
+        );
+        assert_eq!(message.tasks.len(), 1);
+    }
+}
@@ -0,0 +1,474 @@
+use crate::mrkdwn::{Formatting, sanitize};
+use serde_json::{Value, json};
+
+/// Action ID of the checkbox rendered next to each open task
+pub const COMPLETE_TASK_ACTION_ID: &str = "complete_task";
+
+/// Slack rejects messages with more than 50 blocks
+const MAX_BLOCKS: usize = 50;
+
+/// Indents nested lines, Slack drops leading spaces but keeps em spaces
+const INDENT: &str = "\u{2003}\u{2003}";
+
+/// An open task that can be ticked off straight from Slack
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlackTask {
+    /// Todoist task ID, sent back in the interaction payload when the box is ticked
+    pub id: String,
+    /// Task content as it appears after `- [ ]` in the message text
+    pub content: String,
+    /// Priority as returned by the Todoist API, where 4 is urgent and 1 is normal
+    pub priority: u8,
+    pub labels: Vec<String>,
+    pub overdue: bool,
+}
+
+/// A message to post or update
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlackMessage {
+    /// Markdown text of the message
+    pub text: String,
+    /// Open tasks that get a checkbox instead of a plain `- [ ]` line
+    pub tasks: Vec<SlackTask>,
+    /// Plain text shown in notifications and to screen readers, generated from `text` if unset
+    pub fallback_text: Option<String>,
+    /// Post the message as a reply in the thread of this message
+    pub thread_ts: Option<String>,
+}
+
+impl SlackMessage {
+    /// Create a plain message without interactive tasks
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            tasks: Vec::new(),
+            fallback_text: None,
+            thread_ts: None,
+        }
+    }
+
+    /// Render the given open tasks as checkboxes
+    pub fn with_tasks(mut self, tasks: Vec<SlackTask>) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    /// Post the message as a reply in the thread of the message with this timestamp
+    pub fn in_thread(mut self, thread_ts: impl Into<String>) -> Self {
+        self.thread_ts = Some(thread_ts.into());
+        self
+    }
+
+    /// Use the given notification text instead of the generated summary
+    pub fn with_fallback_text(mut self, text: impl Into<String>) -> Self {
+        self.fallback_text = Some(text.into());
+        self
+    }
+}
+
+impl From<&str> for SlackMessage {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Plain-text summary sent as the `text` of a message
+///
+/// Uses the first line that isn't a todo, without formatting, as the title, followed by
+/// the number of open and completed todos, e.g. "Daily Todos - 2025-07-07: 5 open, 3 done".
+pub(crate) fn fallback_text(message: &SlackMessage, formatting: &Formatting) -> String {
+    sanitize(&summary_text(message), &formatting.allowed_mentions)
+}
+
+fn summary_text(message: &SlackMessage) -> String {
+    if let Some(text) = message
+        .fallback_text
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    {
+        return text.to_string();
+    }
+
+    let title = message
+        .text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("- ["))
+        .map(|line| {
+            line.trim_start_matches(|c: char| !c.is_alphanumeric())
+                .replace(['*', '_', '`'], "")
+                .trim()
+                .to_string()
+        })
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+
+    let open = message
+        .text
+        .lines()
+        .filter(|line| line.trim_start().starts_with("- [ ]"))
+        .count();
+    let done = message
+        .text
+        .lines()
+        .filter(|line| line.trim_start().starts_with("- [x]"))
+        .count();
+
+    if open + done == 0 {
+        return title;
+    }
+    let counts = format!("{} open, {} done", open, done);
+    if title.is_empty() {
+        counts
+    } else {
+        format!("{}: {}", title, counts)
+    }
+}
+
+/// Escape a line and convert its markdown checkboxes into the configured icons
+fn render_line(line: &str, formatting: &Formatting) -> String {
+    sanitize(line, &formatting.allowed_mentions)
+        .replace("- [ ]", &formatting.icons.open)
+        .replace("- [x]", &formatting.icons.done)
+}
+
+/// Render an open task with its priority, label and overdue icons
+pub(crate) fn render_task(task: &SlackTask, formatting: &Formatting) -> String {
+    let icons = &formatting.icons;
+    let mut text = format!("{} ", icons.open);
+    if let Some(icon) = icons.priority_icon(task.priority) {
+        text.push_str(icon);
+        text.push(' ');
+    }
+    text.push_str(&sanitize(&task.content, &formatting.allowed_mentions));
+    for icon in task
+        .labels
+        .iter()
+        .filter_map(|label| icons.label_icon(label))
+    {
+        text.push(' ');
+        text.push_str(icon);
+    }
+    if task.overdue && !icons.overdue.is_empty() {
+        text.push(' ');
+        text.push_str(&icons.overdue);
+    }
+    text
+}
+
+pub(crate) fn text_section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": text
+        }
+    })
+}
+
+pub(crate) fn task_section(task: &SlackTask, text: &str) -> Value {
+    json!({
+        "type": "section",
+        "block_id": format!("task_{}", task.id),
+        "text": {
+            "type": "mrkdwn",
+            "text": text
+        },
+        "accessory": {
+            "type": "checkboxes",
+            "action_id": COMPLETE_TASK_ACTION_ID,
+            "options": [
+                {
+                    "text": { "type": "mrkdwn", "text": "Done" },
+                    "value": task.id
+                }
+            ]
+        }
+    })
+}
+
+/// Build the Block Kit blocks for a message
+///
+/// Open task lines with a matching entry in `message.tasks` become their own
+/// section with a checkbox if `interactive` is set; everything else is grouped
+/// into mrkdwn sections. Falls back to a single text section if the result would
+/// exceed Slack's block limit.
+pub(crate) fn build_blocks(
+    message: &SlackMessage,
+    formatting: &Formatting,
+    interactive: bool,
+) -> Vec<Value> {
+    // Each line rendered as text, paired with its task if it gets a checkbox
+    let mut lines: Vec<(String, Option<&SlackTask>)> = Vec::new();
+    let mut remaining_tasks: Vec<&SlackTask> = message.tasks.iter().collect();
+
+    for line in message.text.lines() {
+        let task_index = line.trim().strip_prefix("- [ ]").and_then(|content| {
+            let content = content.trim();
+            remaining_tasks.iter().position(|t| t.content == content)
+        });
+
+        // List items nested with two spaces per level keep their nesting
+        let trimmed = line.trim_start();
+        let indent = if trimmed.starts_with("- ") {
+            INDENT.repeat((line.len() - trimmed.len()) / 2)
+        } else {
+            String::new()
+        };
+
+        match task_index {
+            Some(index) => {
+                let task = remaining_tasks.remove(index);
+                let text = format!("{}{}", indent, render_task(task, formatting));
+                lines.push((text, Some(task)));
+            }
+            None if !indent.is_empty() => lines.push((
+                format!("{}{}", indent, render_line(trimmed, formatting)),
+                None,
+            )),
+            None => lines.push((render_line(line, formatting), None)),
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut pending_lines: Vec<&str> = Vec::new();
+
+    for (text, task) in &lines {
+        match task {
+            Some(task) if interactive => {
+                let pending = pending_lines.join("\n");
+                if !pending.trim().is_empty() {
+                    blocks.push(text_section(&pending));
+                }
+                pending_lines.clear();
+                blocks.push(task_section(task, text));
+            }
+            _ => pending_lines.push(text),
+        }
+    }
+
+    let pending = pending_lines.join("\n");
+    if !pending.trim().is_empty() {
+        blocks.push(text_section(&pending));
+    }
+
+    // Leave room for the trailing divider
+    if blocks.len() >= MAX_BLOCKS {
+        let text: Vec<&str> = lines.iter().map(|(text, _)| text.as_str()).collect();
+        blocks = vec![text_section(&text.join("\n"))];
+    }
+
+    blocks.push(json!({ "type": "divider" }));
+    // {
+    //   "type": "section",
+    //   "text": {
+    //     "type": "mrkdwn",
+    //     "text": "Your daily context is provided by <https://github.com/jvanbaarsen/slaist|Slaist>"
+    //   }
+    // }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, content: &str) -> SlackTask {
+        SlackTask {
+            id: id.to_string(),
+            content: content.to_string(),
+            ..SlackTask::default()
+        }
+    }
+
+    #[test]
+    fn test_fallback_text_summarizes_message() {
+        let message = SlackMessage::new(
+            "📅 *Daily Todos - 2025-07-07*\n\n## Active Todos\n\n- [ ] Task A\n- [ ] Task B\n\n## Completed Todos\n\n- [x] Task C",
+        );
+        assert_eq!(
+            fallback_text(&message, &Formatting::default()),
+            "Daily Todos - 2025-07-07: 2 open, 1 done"
+        );
+
+        assert_eq!(
+            fallback_text(&SlackMessage::new("*Hello*"), &Formatting::default()),
+            "Hello"
+        );
+        assert_eq!(
+            fallback_text(&SlackMessage::new("- [ ] Task A"), &Formatting::default()),
+            "1 open, 0 done"
+        );
+    }
+
+    #[test]
+    fn test_fallback_text_override() {
+        let message = SlackMessage::new("- [ ] Task A").with_fallback_text("1 todo left");
+        assert_eq!(
+            fallback_text(&message, &Formatting::default()),
+            "1 todo left"
+        );
+    }
+
+    #[test]
+    fn test_build_blocks_plain_message() {
+        let blocks = build_blocks(
+            &SlackMessage::new("## Active Todos\n\n- [ ] Task A\n- [x] Task B"),
+            &Formatting::default(),
+            true,
+        );
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "## Active Todos\n\n⬜ Task A\n✅ Task B"
+        );
+        assert_eq!(blocks[1]["type"], "divider");
+    }
+
+    #[test]
+    fn test_build_blocks_with_checkboxes() {
+        let message = SlackMessage::new(
+            "## Active Todos\n\n- [ ] Task A\n- [ ] Task B\n\n## Completed Todos\n\n- [x] Task C",
+        )
+        .with_tasks(vec![task("42", "Task A")]);
+
+        let blocks = build_blocks(&message, &Formatting::default(), true);
+
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[0]["text"]["text"], "## Active Todos\n");
+        assert_eq!(blocks[1]["text"]["text"], "⬜ Task A");
+        assert_eq!(blocks[1]["accessory"]["action_id"], COMPLETE_TASK_ACTION_ID);
+        assert_eq!(blocks[1]["accessory"]["options"][0]["value"], "42");
+        assert!(
+            blocks[2]["text"]["text"]
+                .as_str()
+                .unwrap()
+                .contains("⬜ Task B")
+        );
+        assert!(
+            blocks[2]["text"]["text"]
+                .as_str()
+                .unwrap()
+                .contains("✅ Task C")
+        );
+        assert_eq!(blocks[3]["type"], "divider");
+    }
+
+    #[test]
+    fn test_build_blocks_with_custom_icons() {
+        let icons = crate::IconSet {
+            open: ":todo:".to_string(),
+            done: ":todo_done:".to_string(),
+            overdue: ":fire:".to_string(),
+            labels: [("waiting".to_string(), ":hourglass:".to_string())].into(),
+            ..crate::IconSet::default()
+        };
+        let formatting = Formatting {
+            icons,
+            ..Formatting::default()
+        };
+        let message =
+            SlackMessage::new("- [ ] Urgent task\n- [x] Finished").with_tasks(vec![SlackTask {
+                priority: 4,
+                labels: vec!["waiting".to_string(), "work".to_string()],
+                overdue: true,
+                ..task("1", "Urgent task")
+            }]);
+
+        let blocks = build_blocks(&message, &formatting, true);
+
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            ":todo: 🔴 Urgent task :hourglass: :fire:"
+        );
+        assert_eq!(blocks[1]["text"]["text"], ":todo_done: Finished");
+    }
+
+    #[test]
+    fn test_build_blocks_escapes_task_content() {
+        let message = SlackMessage::new(
+            "- [ ] <!channel> deploy\n- [x] Pay <https://evil|bank>\n- [ ] Ask <@U123>",
+        )
+        .with_tasks(vec![task("1", "<!channel> deploy")]);
+        let formatting = Formatting {
+            allowed_mentions: vec!["U123".to_string()],
+            ..Formatting::default()
+        };
+
+        let blocks = build_blocks(&message, &formatting, true);
+
+        assert_eq!(blocks[0]["text"]["text"], "⬜ &lt;!channel&gt; deploy");
+        assert_eq!(
+            blocks[1]["text"]["text"],
+            "✅ Pay &lt;https://evil|bank&gt;\n⬜ Ask <@U123>"
+        );
+        assert_eq!(
+            fallback_text(
+                &SlackMessage::new("x").with_fallback_text("<!here> & <@U123>"),
+                &formatting
+            ),
+            "&lt;!here&gt; &amp; <@U123>"
+        );
+    }
+
+    #[test]
+    fn test_build_blocks_without_interactivity() {
+        let message = SlackMessage::new("## Active Todos\n\n- [ ] Task A\n- [ ] Task B")
+            .with_tasks(vec![SlackTask {
+                priority: 4,
+                ..task("42", "Task A")
+            }]);
+
+        let blocks = build_blocks(&message, &Formatting::default(), false);
+
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].get("accessory").is_none());
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "## Active Todos\n\n⬜ 🔴 Task A\n⬜ Task B"
+        );
+    }
+
+    #[test]
+    fn test_build_blocks_indents_nested_tasks() {
+        let message = SlackMessage::new("- [ ] Parent\n  - [ ] Child\n    - [x] Grandchild")
+            .with_tasks(vec![task("1", "Parent"), task("2", "Child")]);
+
+        let blocks = build_blocks(&message, &Formatting::default(), true);
+
+        assert_eq!(blocks[0]["text"]["text"], "⬜ Parent");
+        assert_eq!(blocks[1]["text"]["text"], "\u{2003}\u{2003}⬜ Child");
+        assert_eq!(
+            blocks[2]["text"]["text"],
+            "\u{2003}\u{2003}\u{2003}\u{2003}✅ Grandchild"
+        );
+    }
+
+    #[test]
+    fn test_build_blocks_falls_back_when_too_many_tasks() {
+        let tasks: Vec<SlackTask> = (0..60)
+            .map(|i| task(&i.to_string(), &format!("Task {}", i)))
+            .collect();
+        let text = tasks
+            .iter()
+            .map(|t| format!("- [ ] {}", t.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let blocks = build_blocks(
+            &SlackMessage::new(text).with_tasks(tasks),
+            &Formatting::default(),
+            true,
+        );
+
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].get("accessory").is_none());
+        assert!(
+            blocks[0]["text"]["text"]
+                .as_str()
+                .unwrap()
+                .starts_with("⬜ Task 0")
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::SlackError;
+use serde::{Deserialize, Serialize};
+
+/// A slash command invocation, as posted by Slack to the command request URL
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlashCommand {
+    /// The command that was typed, e.g. `/slaist`
+    pub command: String,
+    /// Everything typed after the command
+    #[serde(default)]
+    pub text: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    pub channel_id: String,
+    #[serde(default)]
+    pub team_id: Option<String>,
+    #[serde(default)]
+    pub response_url: Option<String>,
+}
+
+/// Parse the form-encoded body Slack posts to a slash command request URL
+pub fn parse_slash_command(body: &str) -> Result<SlashCommand, SlackError> {
+    serde_urlencoded::from_str(body)
+        .map_err(|e| SlackError::InvalidRequest(format!("Invalid slash command body: {}", e)))
+}
+
+/// Who gets to see the response to a slash command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseType {
+    /// Only visible to the user who ran the command
+    Ephemeral,
+    /// Posted to the channel for everyone to see
+    InChannel,
+}
+
+/// The immediate response to a slash command
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandResponse {
+    pub response_type: ResponseType,
+    pub text: String,
+}
+
+impl CommandResponse {
+    /// A response only the invoking user can see
+    ///
+    /// The text is sent as mrkdwn, so user-supplied parts should be passed through
+    /// [`crate::sanitize`] first.
+    pub fn ephemeral(text: impl Into<String>) -> Self {
+        Self {
+            response_type: ResponseType::Ephemeral,
+            text: text.into(),
+        }
+    }
+
+    /// A response visible to the whole channel
+    ///
+    /// The text is sent as mrkdwn, so user-supplied parts should be passed through
+    /// [`crate::sanitize`] first.
+    pub fn in_channel(text: impl Into<String>) -> Self {
+        Self {
+            response_type: ResponseType::InChannel,
+            text: text.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slash_command() {
+        let body = "token=abc&team_id=T123&channel_id=C123&user_id=U123&user_name=jane\
+                    &command=%2Fslaist&text=add+Write+docs&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2F1";
+
+        let command = parse_slash_command(body).unwrap();
+        assert_eq!(command.command, "/slaist");
+        assert_eq!(command.text, "add Write docs");
+        assert_eq!(command.user_id, "U123");
+        assert_eq!(command.channel_id, "C123");
+        assert_eq!(
+            command.response_url.as_deref(),
+            Some("https://hooks.slack.com/commands/1")
+        );
+    }
+
+    #[test]
+    fn test_parse_slash_command_without_text() {
+        let command =
+            parse_slash_command("command=%2Fslaist&user_id=U123&channel_id=C123").unwrap();
+        assert_eq!(command.text, "");
+    }
+
+    #[test]
+    fn test_command_response_serialization() {
+        let json = serde_json::to_value(CommandResponse::in_channel("Posted")).unwrap();
+        assert_eq!(json["response_type"], "in_channel");
+        assert_eq!(json["text"], "Posted");
+
+        let json = serde_json::to_value(CommandResponse::ephemeral("Only you")).unwrap();
+        assert_eq!(json["response_type"], "ephemeral");
+    }
+
+    #[test]
+    fn test_command_response_keeps_mrkdwn() {
+        let response = CommandResponse::in_channel("📅 <@U123> is posting");
+        assert_eq!(response.text, "📅 <@U123> is posting");
+    }
+}
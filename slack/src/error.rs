@@ -0,0 +1,314 @@
+use std::time::Duration;
+
+/// Known values of the `error` field in Slack Web API responses
+///
+/// Codes without a dedicated variant are kept in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiErrorCode {
+    // Authentication
+    InvalidAuth,
+    NotAuthed,
+    AccountInactive,
+    TokenRevoked,
+    TokenExpired,
+    MissingScope,
+    NotAllowedTokenType,
+    // Channels
+    ChannelNotFound,
+    NotInChannel,
+    IsArchived,
+    RestrictedAction,
+    // Messages
+    MessageNotFound,
+    CantUpdateMessage,
+    CantDeleteMessage,
+    EditWindowClosed,
+    MsgTooLong,
+    NoText,
+    InvalidBlocks,
+    TooManyAttachments,
+    // Users
+    UserNotFound,
+    // Transient failures on Slack's side
+    InternalError,
+    FatalError,
+    ServiceUnavailable,
+    RequestTimeout,
+    Other(String),
+}
+
+impl ApiErrorCode {
+    /// Map the `error` string of an API response to a code
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "invalid_auth" => ApiErrorCode::InvalidAuth,
+            "not_authed" => ApiErrorCode::NotAuthed,
+            "account_inactive" => ApiErrorCode::AccountInactive,
+            "token_revoked" => ApiErrorCode::TokenRevoked,
+            "token_expired" => ApiErrorCode::TokenExpired,
+            "missing_scope" => ApiErrorCode::MissingScope,
+            "not_allowed_token_type" => ApiErrorCode::NotAllowedTokenType,
+            "channel_not_found" => ApiErrorCode::ChannelNotFound,
+            "not_in_channel" => ApiErrorCode::NotInChannel,
+            "is_archived" => ApiErrorCode::IsArchived,
+            "restricted_action" => ApiErrorCode::RestrictedAction,
+            "message_not_found" => ApiErrorCode::MessageNotFound,
+            "cant_update_message" => ApiErrorCode::CantUpdateMessage,
+            "cant_delete_message" => ApiErrorCode::CantDeleteMessage,
+            "edit_window_closed" => ApiErrorCode::EditWindowClosed,
+            "msg_too_long" => ApiErrorCode::MsgTooLong,
+            "no_text" => ApiErrorCode::NoText,
+            "invalid_blocks" => ApiErrorCode::InvalidBlocks,
+            "too_many_attachments" => ApiErrorCode::TooManyAttachments,
+            "user_not_found" => ApiErrorCode::UserNotFound,
+            "internal_error" => ApiErrorCode::InternalError,
+            "fatal_error" => ApiErrorCode::FatalError,
+            "service_unavailable" => ApiErrorCode::ServiceUnavailable,
+            "request_timeout" => ApiErrorCode::RequestTimeout,
+            other => ApiErrorCode::Other(other.to_string()),
+        }
+    }
+
+    /// The code as Slack spells it
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiErrorCode::InvalidAuth => "invalid_auth",
+            ApiErrorCode::NotAuthed => "not_authed",
+            ApiErrorCode::AccountInactive => "account_inactive",
+            ApiErrorCode::TokenRevoked => "token_revoked",
+            ApiErrorCode::TokenExpired => "token_expired",
+            ApiErrorCode::MissingScope => "missing_scope",
+            ApiErrorCode::NotAllowedTokenType => "not_allowed_token_type",
+            ApiErrorCode::ChannelNotFound => "channel_not_found",
+            ApiErrorCode::NotInChannel => "not_in_channel",
+            ApiErrorCode::IsArchived => "is_archived",
+            ApiErrorCode::RestrictedAction => "restricted_action",
+            ApiErrorCode::MessageNotFound => "message_not_found",
+            ApiErrorCode::CantUpdateMessage => "cant_update_message",
+            ApiErrorCode::CantDeleteMessage => "cant_delete_message",
+            ApiErrorCode::EditWindowClosed => "edit_window_closed",
+            ApiErrorCode::MsgTooLong => "msg_too_long",
+            ApiErrorCode::NoText => "no_text",
+            ApiErrorCode::InvalidBlocks => "invalid_blocks",
+            ApiErrorCode::TooManyAttachments => "too_many_attachments",
+            ApiErrorCode::UserNotFound => "user_not_found",
+            ApiErrorCode::InternalError => "internal_error",
+            ApiErrorCode::FatalError => "fatal_error",
+            ApiErrorCode::ServiceUnavailable => "service_unavailable",
+            ApiErrorCode::RequestTimeout => "request_timeout",
+            ApiErrorCode::Other(code) => code,
+        }
+    }
+
+    /// Whether the token is invalid or lacks permissions, so retrying won't help
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::InvalidAuth
+                | ApiErrorCode::NotAuthed
+                | ApiErrorCode::AccountInactive
+                | ApiErrorCode::TokenRevoked
+                | ApiErrorCode::TokenExpired
+                | ApiErrorCode::MissingScope
+                | ApiErrorCode::NotAllowedTokenType
+        )
+    }
+
+    /// Whether the same request may succeed if sent again later
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::InternalError
+                | ApiErrorCode::FatalError
+                | ApiErrorCode::ServiceUnavailable
+                | ApiErrorCode::RequestTimeout
+        )
+    }
+}
+
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub enum SlackError {
+    HttpError(reqwest::Error),
+    /// Slack answered with `"ok": false`
+    ApiError(ApiErrorCode),
+    /// Slack answered with HTTP 429 or the `ratelimited` error code
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    /// Slack answered with an unexpected HTTP status and no API response
+    ServerError(u16),
+    /// Slack answered with `"ok": true` but without the expected data
+    InvalidResponse(String),
+    ConfigError(String),
+    SerializationError(serde_json::Error),
+    SignatureError(String),
+    InvalidRequest(String),
+    WebSocketError(String),
+}
+
+impl SlackError {
+    /// The API error code, if Slack returned one
+    pub fn code(&self) -> Option<&ApiErrorCode> {
+        match self {
+            SlackError::ApiError(code) => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SlackError::RateLimited { .. } => true,
+            SlackError::ServerError(status) => *status >= 500,
+            SlackError::ApiError(code) => code.is_transient(),
+            // The request never reached Slack, so it is safe to send again
+            SlackError::HttpError(e) => e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Whether Slack certainly didn't act on the request, so that even a request
+    /// that isn't safe to repeat, like posting a message, can be sent again
+    pub fn was_not_processed(&self) -> bool {
+        match self {
+            SlackError::RateLimited { .. } => true,
+            SlackError::HttpError(e) => e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// How long Slack asked us to wait before retrying, if it did
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            SlackError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SlackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackError::HttpError(e) => write!(f, "HTTP error: {}", e),
+            SlackError::ApiError(e) => write!(f, "Slack API error: {}", e),
+            SlackError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "Rate limited by Slack (retry after {}s)",
+                retry_after.as_secs()
+            ),
+            SlackError::RateLimited { retry_after: None } => write!(f, "Rate limited by Slack"),
+            SlackError::ServerError(status) => write!(f, "Slack returned HTTP {}", status),
+            SlackError::InvalidResponse(e) => write!(f, "Invalid Slack response: {}", e),
+            SlackError::ConfigError(e) => write!(f, "Configuration error: {}", e),
+            SlackError::SerializationError(e) => write!(f, "Serialization error: {}", e),
+            SlackError::SignatureError(e) => write!(f, "Signature verification failed: {}", e),
+            SlackError::InvalidRequest(e) => write!(f, "Invalid request: {}", e),
+            SlackError::WebSocketError(e) => write!(f, "WebSocket error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SlackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SlackError::HttpError(e) => Some(e),
+            SlackError::SerializationError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for SlackError {
+    fn from(err: reqwest::Error) -> Self {
+        SlackError::HttpError(err)
+    }
+}
+
+impl From<serde_json::Error> for SlackError {
+    fn from(err: serde_json::Error) -> Self {
+        SlackError::SerializationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_api_error_code_round_trip() {
+        for code in [
+            "invalid_auth",
+            "channel_not_found",
+            "not_in_channel",
+            "message_not_found",
+            "cant_update_message",
+            "msg_too_long",
+            "service_unavailable",
+        ] {
+            assert_eq!(ApiErrorCode::from_code(code).as_str(), code);
+        }
+
+        assert_eq!(
+            ApiErrorCode::from_code("something_new"),
+            ApiErrorCode::Other("something_new".to_string())
+        );
+        assert_eq!(
+            ApiErrorCode::from_code("something_new").to_string(),
+            "something_new"
+        );
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(
+            SlackError::RateLimited {
+                retry_after: Some(Duration::from_secs(3))
+            }
+            .is_transient()
+        );
+        assert!(SlackError::ServerError(503).is_transient());
+        assert!(SlackError::ApiError(ApiErrorCode::InternalError).is_transient());
+
+        assert!(!SlackError::ServerError(404).is_transient());
+        assert!(!SlackError::ApiError(ApiErrorCode::ChannelNotFound).is_transient());
+        assert!(!SlackError::ApiError(ApiErrorCode::InvalidAuth).is_transient());
+        assert!(!SlackError::ConfigError("missing".to_string()).is_transient());
+
+        assert!(SlackError::RateLimited { retry_after: None }.was_not_processed());
+        assert!(!SlackError::ServerError(503).was_not_processed());
+        assert!(!SlackError::ApiError(ApiErrorCode::InternalError).was_not_processed());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let error = SlackError::RateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(30)));
+        assert_eq!(error.to_string(), "Rate limited by Slack (retry after 30s)");
+        assert_eq!(
+            SlackError::ApiError(ApiErrorCode::MsgTooLong).retry_after(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_error_source() {
+        let json_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        assert!(SlackError::from(json_error).source().is_some());
+        assert!(
+            SlackError::ApiError(ApiErrorCode::NotInChannel)
+                .source()
+                .is_none()
+        );
+    }
+}
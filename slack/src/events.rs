@@ -0,0 +1,188 @@
+use crate::SlackError;
+use serde::Deserialize;
+
+/// Body Slack posts to the Events API request URL
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventPayload {
+    /// Sent once when the request URL is configured; must be answered with the challenge
+    UrlVerification {
+        challenge: String,
+    },
+    EventCallback(EventCallback),
+    #[serde(other)]
+    Unsupported,
+}
+
+/// An `event_callback` from the Events API
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventCallback {
+    #[serde(default)]
+    pub team_id: Option<String>,
+    #[serde(default)]
+    pub event_id: Option<String>,
+    pub event: Event,
+}
+
+/// The inner event of an `event_callback`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    AppMention(AppMention),
+    ReactionAdded(ReactionAdded),
+    AppHomeOpened(AppHomeOpened),
+    #[serde(other)]
+    Unsupported,
+}
+
+/// The bot was mentioned in a message
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppMention {
+    #[serde(default)]
+    pub user: Option<String>,
+    pub text: String,
+    pub channel: String,
+    pub ts: String,
+    #[serde(default)]
+    pub thread_ts: Option<String>,
+}
+
+/// Someone added an emoji reaction to an item
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReactionAdded {
+    pub user: String,
+    /// Emoji name without colons, e.g. `heavy_plus_sign`
+    pub reaction: String,
+    pub item: ReactionItem,
+}
+
+/// A user opened one of the app's tabs
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppHomeOpened {
+    pub user: String,
+    /// Either `home` or `messages`
+    #[serde(default)]
+    pub tab: String,
+}
+
+/// The item a reaction was added to
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReactionItem {
+    Message {
+        channel: String,
+        ts: String,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Parse the JSON body Slack posts to the Events API request URL
+pub fn parse_event_payload(body: &str) -> Result<EventPayload, SlackError> {
+    Ok(serde_json::from_str(body)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url_verification() {
+        let body = r#"{"token":"abc","challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P","type":"url_verification"}"#;
+
+        let EventPayload::UrlVerification { challenge } = parse_event_payload(body).unwrap() else {
+            panic!("Expected url_verification payload");
+        };
+        assert_eq!(
+            challenge,
+            "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"
+        );
+    }
+
+    #[test]
+    fn test_parse_app_mention() {
+        let body = r#"{
+            "type": "event_callback",
+            "team_id": "T123",
+            "event_id": "Ev123",
+            "event": {
+                "type": "app_mention",
+                "user": "U123",
+                "text": "<@U0BOT> todo: Review the release notes",
+                "ts": "1515449522.000016",
+                "channel": "C123",
+                "event_ts": "1515449522000016"
+            }
+        }"#;
+
+        let EventPayload::EventCallback(callback) = parse_event_payload(body).unwrap() else {
+            panic!("Expected event_callback payload");
+        };
+        let Event::AppMention(mention) = callback.event else {
+            panic!("Expected app_mention event");
+        };
+        assert_eq!(mention.text, "<@U0BOT> todo: Review the release notes");
+        assert_eq!(mention.channel, "C123");
+        assert_eq!(mention.ts, "1515449522.000016");
+    }
+
+    #[test]
+    fn test_parse_reaction_added() {
+        let body = r#"{
+            "type": "event_callback",
+            "event": {
+                "type": "reaction_added",
+                "user": "U123",
+                "reaction": "heavy_plus_sign",
+                "item_user": "U456",
+                "item": { "type": "message", "channel": "C123", "ts": "1360782400.498405" },
+                "event_ts": "1360782804.083113"
+            }
+        }"#;
+
+        let EventPayload::EventCallback(callback) = parse_event_payload(body).unwrap() else {
+            panic!("Expected event_callback payload");
+        };
+        let Event::ReactionAdded(reaction) = callback.event else {
+            panic!("Expected reaction_added event");
+        };
+        assert_eq!(reaction.reaction, "heavy_plus_sign");
+        assert!(matches!(
+            reaction.item,
+            ReactionItem::Message { ref channel, ref ts } if channel == "C123" && ts == "1360782400.498405"
+        ));
+    }
+
+    #[test]
+    fn test_parse_app_home_opened() {
+        let body = r#"{
+            "type": "event_callback",
+            "event": {
+                "type": "app_home_opened",
+                "user": "U123",
+                "channel": "D123",
+                "event_ts": "1515449522000016",
+                "tab": "home"
+            }
+        }"#;
+
+        let EventPayload::EventCallback(callback) = parse_event_payload(body).unwrap() else {
+            panic!("Expected event_callback payload");
+        };
+        let Event::AppHomeOpened(opened) = callback.event else {
+            panic!("Expected app_home_opened event");
+        };
+        assert_eq!(opened.user, "U123");
+        assert_eq!(opened.tab, "home");
+    }
+
+    #[test]
+    fn test_parse_unsupported_event() {
+        let body = r#"{"type":"event_callback","event":{"type":"channel_created","channel":{}}}"#;
+
+        let EventPayload::EventCallback(callback) = parse_event_payload(body).unwrap() else {
+            panic!("Expected event_callback payload");
+        };
+        assert!(matches!(callback.event, Event::Unsupported));
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// A file to share through the upload flow of `files.getUploadURLExternal`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileUpload {
+    pub filename: String,
+    /// Title shown in Slack, defaults to the filename
+    pub title: Option<String>,
+    pub content: Vec<u8>,
+    /// Syntax used to render a text snippet, e.g. `markdown`
+    pub snippet_type: Option<String>,
+}
+
+impl FileUpload {
+    /// A file with the given name and contents
+    pub fn new(filename: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        Self {
+            filename: filename.into(),
+            content: content.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Show the file under a different title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Render the file as a snippet with the given syntax, e.g. `markdown`
+    pub fn with_snippet_type(mut self, snippet_type: impl Into<String>) -> Self {
+        self.snippet_type = Some(snippet_type.into());
+        self
+    }
+}
+
+/// Where a message ended up after posting or updating it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostedMessage {
+    /// Channel ID, even if the message was posted to a channel name
+    pub channel: String,
+    /// Timestamp identifying the message
+    pub ts: String,
+}
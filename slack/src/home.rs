@@ -0,0 +1,168 @@
+use crate::SlackTask;
+use crate::blocks::{render_task, task_section, text_section};
+use crate::mrkdwn::{Formatting, sanitize};
+use serde_json::{Value, json};
+
+/// Slack rejects Home tabs with more than 100 blocks
+const MAX_HOME_BLOCKS: usize = 100;
+
+/// Content of the app's Home tab
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HomeTab {
+    pub title: String,
+    /// Shown in small print below the title, e.g. when the data was synced
+    pub subtitle: Option<String>,
+    /// Open tasks grouped by project, in display order
+    pub projects: Vec<HomeProject>,
+    /// Content of the tasks completed today
+    pub completed: Vec<String>,
+}
+
+/// Open tasks of a single project
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HomeProject {
+    pub name: String,
+    pub tasks: Vec<SlackTask>,
+}
+
+/// Render an open task, in bold if it is overdue
+fn render_home_task(task: &SlackTask, formatting: &Formatting) -> String {
+    let text = render_task(task, formatting);
+    if !task.overdue {
+        return text;
+    }
+    // Bold the escaped content, leaving the icons around it untouched
+    let content = sanitize(&task.content, &formatting.allowed_mentions);
+    text.replacen(&content, &format!("*{}*", content), 1)
+}
+
+/// Build the `home` view for `views.publish`
+///
+/// Open tasks get a checkbox like in the daily message. Projects are rendered as
+/// plain text instead if the tab would exceed Slack's block limit.
+pub(crate) fn build_home_view(home: &HomeTab, formatting: &Formatting) -> Value {
+    let icons = &formatting.icons;
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": home.title, "emoji": true }
+    })];
+    if let Some(subtitle) = &home.subtitle {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": sanitize(subtitle, &formatting.allowed_mentions) }]
+        }));
+    }
+
+    let overdue = home
+        .projects
+        .iter()
+        .flat_map(|project| &project.tasks)
+        .filter(|task| task.overdue)
+        .count();
+    if overdue > 0 {
+        let text = format!("{} *{} overdue*", icons.overdue, overdue);
+        blocks.push(text_section(text.trim_start()));
+    }
+
+    // Headers, dividers and the completed section take a few blocks of their own
+    let task_count: usize = home.projects.iter().map(|p| p.tasks.len()).sum();
+    let interactive = blocks.len() + home.projects.len() + task_count + 4 <= MAX_HOME_BLOCKS;
+
+    if home.projects.is_empty() {
+        blocks.push(text_section("_No active todos found! 🎉_"));
+    }
+    for project in &home.projects {
+        if interactive {
+            blocks.push(text_section(&format!(
+                "*{}*",
+                sanitize(&project.name, &formatting.allowed_mentions)
+            )));
+            for task in &project.tasks {
+                blocks.push(task_section(task, &render_home_task(task, formatting)));
+            }
+        } else {
+            let mut lines = vec![format!(
+                "*{}*",
+                sanitize(&project.name, &formatting.allowed_mentions)
+            )];
+            lines.extend(
+                project
+                    .tasks
+                    .iter()
+                    .map(|t| render_home_task(t, formatting)),
+            );
+            blocks.push(text_section(&lines.join("\n")));
+        }
+    }
+
+    blocks.push(json!({ "type": "divider" }));
+    let completed = if home.completed.is_empty() {
+        "_No completed todos yet._".to_string()
+    } else {
+        home.completed
+            .iter()
+            .map(|content| {
+                format!(
+                    "{} {}",
+                    icons.done,
+                    sanitize(content, &formatting.allowed_mentions)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    blocks.push(text_section(&format!("*Completed today*\n{}", completed)));
+
+    json!({ "type": "home", "blocks": blocks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMPLETE_TASK_ACTION_ID;
+
+    fn home_tab(tasks: usize) -> HomeTab {
+        HomeTab {
+            title: "Daily Todos - 2025-07-07".to_string(),
+            subtitle: Some("Last synced 09:00".to_string()),
+            projects: vec![HomeProject {
+                name: "Work".to_string(),
+                tasks: (0..tasks)
+                    .map(|i| SlackTask {
+                        id: i.to_string(),
+                        content: format!("Task {}", i),
+                        overdue: i == 0,
+                        ..SlackTask::default()
+                    })
+                    .collect(),
+            }],
+            completed: vec!["Done task".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_build_home_view() {
+        let view = build_home_view(&home_tab(2), &Formatting::default());
+        let blocks = view["blocks"].as_array().unwrap();
+
+        assert_eq!(view["type"], "home");
+        assert_eq!(blocks[0]["text"]["text"], "Daily Todos - 2025-07-07");
+        assert_eq!(blocks[1]["elements"][0]["text"], "Last synced 09:00");
+        assert_eq!(blocks[2]["text"]["text"], "⏰ *1 overdue*");
+        assert_eq!(blocks[3]["text"]["text"], "*Work*");
+        assert_eq!(blocks[4]["text"]["text"], "⬜ *Task 0* ⏰");
+        assert_eq!(blocks[4]["accessory"]["action_id"], COMPLETE_TASK_ACTION_ID);
+        assert_eq!(blocks[5]["text"]["text"], "⬜ Task 1");
+        assert_eq!(blocks[6]["type"], "divider");
+        assert_eq!(blocks[7]["text"]["text"], "*Completed today*\n✅ Done task");
+    }
+
+    #[test]
+    fn test_build_home_view_falls_back_to_text() {
+        let view = build_home_view(&home_tab(120), &Formatting::default());
+        let blocks = view["blocks"].as_array().unwrap();
+
+        assert!(blocks.len() <= MAX_HOME_BLOCKS);
+        assert!(blocks.iter().all(|block| block.get("accessory").is_none()));
+    }
+}
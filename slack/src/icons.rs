@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Emoji used when rendering todos in Slack
+///
+/// Values can be Unicode emoji or workspace emoji codes like `:todo:`.
+/// Anything not configured falls back to Unicode, which works in every workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IconSet {
+    /// Prefix for open todos
+    pub open: String,
+    /// Prefix for completed todos
+    pub done: String,
+    /// Appended to todos that are past their due date
+    pub overdue: String,
+    /// Shown before the content, keyed by Todoist priority as shown in the app ("p1" is urgent)
+    pub priority: BTreeMap<String, String>,
+    /// Appended for each label that has an icon; labels without one are not shown
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Default for IconSet {
+    fn default() -> Self {
+        Self {
+            open: "⬜".to_string(),
+            done: "✅".to_string(),
+            overdue: "⏰".to_string(),
+            priority: BTreeMap::from([
+                ("p1".to_string(), "🔴".to_string()),
+                ("p2".to_string(), "🟠".to_string()),
+                ("p3".to_string(), "🔵".to_string()),
+            ]),
+            labels: BTreeMap::new(),
+        }
+    }
+}
+
+impl IconSet {
+    /// Icon for a priority as returned by the Todoist API, where 4 is urgent and 1 is normal
+    pub fn priority_icon(&self, api_priority: u8) -> Option<&str> {
+        if !(1..=4).contains(&api_priority) {
+            return None;
+        }
+        let key = format!("p{}", 5 - api_priority);
+        self.priority
+            .get(&key)
+            .map(String::as_str)
+            .filter(|icon| !icon.is_empty())
+    }
+
+    /// Icon configured for a label, if any
+    pub fn label_icon(&self, label: &str) -> Option<&str> {
+        self.labels
+            .get(label)
+            .map(String::as_str)
+            .filter(|icon| !icon.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_icons_are_unicode() {
+        let icons = IconSet::default();
+        assert!(!icons.open.contains(':'));
+        assert!(!icons.done.contains(':'));
+        assert_eq!(icons.priority_icon(4), Some("🔴"));
+        assert_eq!(icons.priority_icon(1), None);
+    }
+
+    #[test]
+    fn test_partial_config_falls_back_to_defaults() {
+        let icons: IconSet = serde_json::from_value(serde_json::json!({
+            "open": ":todo:",
+            "labels": { "waiting": ":hourglass:" }
+        }))
+        .unwrap();
+
+        assert_eq!(icons.open, ":todo:");
+        assert_eq!(icons.done, "✅");
+        assert_eq!(icons.label_icon("waiting"), Some(":hourglass:"));
+        assert_eq!(icons.label_icon("other"), None);
+        assert_eq!(icons.priority_icon(3), Some("🟠"));
+    }
+
+    #[test]
+    fn test_priority_icon_out_of_range() {
+        let icons = IconSet::default();
+        assert_eq!(icons.priority_icon(0), None);
+        assert_eq!(icons.priority_icon(5), None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::SlackError;
+use crate::blocks::COMPLETE_TASK_ACTION_ID;
+use serde::Deserialize;
+
+/// Payload Slack sends to the interactivity request URL
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InteractionPayload {
+    BlockActions(BlockActions),
+    #[serde(other)]
+    Unsupported,
+}
+
+/// A `block_actions` interaction, sent when a user clicks a button or ticks a checkbox
+#[derive(Debug, Deserialize)]
+pub struct BlockActions {
+    pub user: InteractionUser,
+    pub channel: Option<InteractionChannel>,
+    pub message: Option<InteractionMessage>,
+    #[serde(default)]
+    pub actions: Vec<BlockAction>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InteractionUser {
+    pub id: String,
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InteractionChannel {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InteractionMessage {
+    pub ts: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BlockAction {
+    pub action_id: String,
+    #[serde(default)]
+    pub block_id: Option<String>,
+    /// Set for buttons
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Set for checkboxes: all options that are ticked after the interaction
+    #[serde(default)]
+    pub selected_options: Vec<SelectedOption>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SelectedOption {
+    pub value: String,
+}
+
+impl BlockActions {
+    /// IDs of the tasks the user ticked off in this interaction
+    pub fn completed_task_ids(&self) -> Vec<String> {
+        self.actions
+            .iter()
+            .filter(|action| action.action_id == COMPLETE_TASK_ACTION_ID)
+            .flat_map(|action| {
+                action
+                    .selected_options
+                    .iter()
+                    .map(|option| option.value.clone())
+                    .chain(action.value.clone())
+            })
+            .collect()
+    }
+}
+
+/// Parse the form-encoded body Slack posts to the interactivity request URL
+pub fn parse_interaction_payload(body: &str) -> Result<InteractionPayload, SlackError> {
+    #[derive(Deserialize)]
+    struct InteractionForm {
+        payload: String,
+    }
+
+    let form: InteractionForm = serde_urlencoded::from_str(body)
+        .map_err(|e| SlackError::InvalidRequest(format!("Invalid form body: {}", e)))?;
+
+    Ok(serde_json::from_str(&form.payload)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_payload(payload: &serde_json::Value) -> String {
+        serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap()
+    }
+
+    #[test]
+    fn test_parse_checkbox_interaction() {
+        let body = encode_payload(&serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123", "username": "jane" },
+            "channel": { "id": "C123" },
+            "message": { "ts": "1234567890.123456" },
+            "actions": [{
+                "type": "checkboxes",
+                "action_id": "complete_task",
+                "block_id": "task_42",
+                "selected_options": [{ "value": "42", "text": { "type": "mrkdwn", "text": "Done" } }]
+            }]
+        }));
+
+        let payload = parse_interaction_payload(&body).unwrap();
+        let InteractionPayload::BlockActions(actions) = payload else {
+            panic!("Expected block actions payload");
+        };
+
+        assert_eq!(actions.user.id, "U123");
+        assert_eq!(actions.channel.as_ref().unwrap().id, "C123");
+        assert_eq!(actions.completed_task_ids(), vec!["42".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_ignores_other_actions() {
+        let body = encode_payload(&serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123" },
+            "actions": [{ "action_id": "something_else", "value": "42" }]
+        }));
+
+        let InteractionPayload::BlockActions(actions) = parse_interaction_payload(&body).unwrap()
+        else {
+            panic!("Expected block actions payload");
+        };
+        assert!(actions.completed_task_ids().is_empty());
+    }
+
+    #[test]
+    fn test_parse_unsupported_payload_type() {
+        let body = encode_payload(&serde_json::json!({ "type": "view_submission" }));
+        assert!(matches!(
+            parse_interaction_payload(&body).unwrap(),
+            InteractionPayload::Unsupported
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_body() {
+        assert!(parse_interaction_payload("not a form").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::IconSet;
+
+/// How user-supplied text is turned into Slack mrkdwn
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Formatting {
+    pub icons: IconSet,
+    /// User, user group or special mention (`here`, `channel`, `everyone`) IDs that may ping
+    pub allowed_mentions: Vec<String>,
+}
+
+/// Escape the characters Slack treats as control sequences in mrkdwn
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Make user-supplied text safe to send as mrkdwn
+///
+/// Markdown links with an http(s) or mailto URL become Slack links, mentions of
+/// allowed IDs like `<@U123>` are kept, and everything else is escaped so it
+/// can't ping people or disguise links.
+pub fn sanitize(text: &str, allowed_mentions: &[String]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find(['<', '[', '&', '>']) {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if let Some((mention, remaining)) = allowed_mention(rest, allowed_mentions) {
+            result.push_str(mention);
+            rest = remaining;
+        } else if let Some((link, remaining)) = markdown_link(rest) {
+            result.push_str(&link);
+            rest = remaining;
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            result.push_str(&escape(&c.to_string()));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Split off a leading `<@U123>`, `<!subteam^S123>` or `<!here>` if its ID is allowed
+fn allowed_mention<'a>(text: &'a str, allowed_mentions: &[String]) -> Option<(&'a str, &'a str)> {
+    let end = text.find('>')?;
+    let inner = text.strip_prefix('<')?.get(..end - 1)?;
+    if inner.contains('<') {
+        return None;
+    }
+    // Mentions may carry a fallback label after `|`
+    let id = inner.split('|').next()?;
+    let id = id
+        .strip_prefix('@')
+        .or_else(|| id.strip_prefix("!subteam^"))
+        .or_else(|| id.strip_prefix('!'))?;
+
+    allowed_mentions
+        .iter()
+        .any(|allowed| allowed == id)
+        .then(|| text.split_at(end + 1))
+}
+
+/// Convert a leading markdown link `[label](url)` into Slack's `<url|label>`
+fn markdown_link(text: &str) -> Option<(String, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let label = &rest[..label_end];
+    let rest = &rest[label_end + 2..];
+    let url_end = rest.find(')')?;
+    let url = &rest[..url_end];
+
+    if label.is_empty() || label.contains(['\n', '[', ']']) {
+        return None;
+    }
+    if !["https://", "http://", "mailto:"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+        || url.contains(char::is_whitespace)
+    {
+        return None;
+    }
+
+    let url = escape(url).replace('|', "%7C");
+    Some((format!("<{}|{}>", url, escape(label)), &rest[url_end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("Q&A <b> ok"), "Q&amp;A &lt;b&gt; ok");
+    }
+
+    #[test]
+    fn test_sanitize_blocks_mentions_and_spoofed_links() {
+        assert_eq!(
+            sanitize("<!channel> deploy", &[]),
+            "&lt;!channel&gt; deploy"
+        );
+        assert_eq!(
+            sanitize("Pay <https://evil|bank>", &[]),
+            "Pay &lt;https://evil|bank&gt;"
+        );
+        assert_eq!(sanitize("Ping <@U123>", &[]), "Ping &lt;@U123&gt;");
+    }
+
+    #[test]
+    fn test_sanitize_keeps_allowed_mentions() {
+        let allowed = vec!["U123".to_string(), "here".to_string(), "S456".to_string()];
+
+        assert_eq!(sanitize("Ask <@U123>", &allowed), "Ask <@U123>");
+        assert_eq!(sanitize("Ask <@U123|jane>", &allowed), "Ask <@U123|jane>");
+        assert_eq!(sanitize("&@U123>", &allowed), "&amp;@U123&gt;");
+        assert_eq!(sanitize("<<@U123>", &allowed), "&lt;<@U123>");
+        assert_eq!(sanitize("<!here> standup", &allowed), "<!here> standup");
+        assert_eq!(
+            sanitize("<!subteam^S456> review", &allowed),
+            "<!subteam^S456> review"
+        );
+        assert_eq!(
+            sanitize("<!channel> and <@U999>", &allowed),
+            "&lt;!channel&gt; and &lt;@U999&gt;"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_converts_markdown_links() {
+        assert_eq!(
+            sanitize("Read [the docs](https://example.com/a?b=1&c=2) now", &[]),
+            "Read <https://example.com/a?b=1&amp;c=2|the docs> now"
+        );
+        assert_eq!(
+            sanitize("[a <b>](https://example.com/x|y)", &[]),
+            "<https://example.com/x%7Cy|a &lt;b&gt;>"
+        );
+        // Only web and mail links are converted
+        assert_eq!(
+            sanitize("[click](javascript:alert(1))", &[]),
+            "[click](javascript:alert(1))"
+        );
+        assert_eq!(sanitize("[x] done", &[]), "[x] done");
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+
+/// Slack limits status texts to 100 characters
+pub const MAX_STATUS_TEXT_LEN: usize = 100;
+
+/// The custom status shown next to a user's name
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SlackStatus {
+    #[serde(default, rename = "status_text")]
+    pub text: String,
+    /// Emoji code with colons, e.g. `:dart:`
+    #[serde(default, rename = "status_emoji")]
+    pub emoji: String,
+    /// Unix timestamp when the status clears by itself, 0 for never
+    #[serde(default, rename = "status_expiration")]
+    pub expiration: i64,
+}
+
+impl SlackStatus {
+    /// A status that never expires, with the text cut to Slack's limit
+    pub fn new(text: &str, emoji: impl Into<String>) -> Self {
+        Self {
+            text: text.chars().take(MAX_STATUS_TEXT_LEN).collect(),
+            emoji: emoji.into(),
+            expiration: 0,
+        }
+    }
+
+    /// Whether no status is set
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.emoji.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_from_profile() {
+        let status: SlackStatus = serde_json::from_str(
+            r#"{"real_name":"Jane","status_text":"Deep work","status_emoji":":dart:","status_expiration":0}"#,
+        )
+        .unwrap();
+
+        assert_eq!(status, SlackStatus::new("Deep work", ":dart:"));
+        assert!(!status.is_empty());
+        assert!(
+            serde_json::from_str::<SlackStatus>("{}")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_status_text_is_truncated() {
+        let status = SlackStatus::new(&"é".repeat(150), ":dart:");
+        assert_eq!(status.text.chars().count(), MAX_STATUS_TEXT_LEN);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::SlackError;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Version prefix Slack uses for request signatures
+const SIGNATURE_VERSION: &str = "v0";
+
+/// Requests with a timestamp further than this from now are rejected to prevent replays
+pub const MAX_REQUEST_AGE_SECS: u64 = 60 * 5;
+
+/// Compute the `X-Slack-Signature` value for a request body
+///
+/// Mostly useful for building signed fixture payloads in tests
+pub fn compute_signature(signing_secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let digest = hex::encode(
+        new_mac(signing_secret, timestamp, body)
+            .finalize()
+            .into_bytes(),
+    );
+    format!("{}={}", SIGNATURE_VERSION, digest)
+}
+
+/// Verify a request signed by Slack
+///
+/// # Arguments
+/// * `signing_secret` - The app's signing secret from the "Basic Information" page
+/// * `timestamp` - Value of the `X-Slack-Request-Timestamp` header
+/// * `body` - The raw request body
+/// * `signature` - Value of the `X-Slack-Signature` header
+pub fn verify_signature(
+    signing_secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+) -> Result<(), SlackError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    verify_signature_at(signing_secret, timestamp, body, signature, now)
+}
+
+/// Verify a request signed by Slack against an explicit current time (in Unix seconds)
+pub fn verify_signature_at(
+    signing_secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+    now: u64,
+) -> Result<(), SlackError> {
+    let request_time: u64 = timestamp.trim().parse().map_err(|_| {
+        SlackError::SignatureError(format!("Invalid request timestamp: {}", timestamp))
+    })?;
+
+    if now.abs_diff(request_time) > MAX_REQUEST_AGE_SECS {
+        return Err(SlackError::SignatureError(
+            "Request timestamp is outside the allowed window".to_string(),
+        ));
+    }
+
+    let expected = signature
+        .strip_prefix(SIGNATURE_VERSION)
+        .and_then(|s| s.strip_prefix('='))
+        .and_then(|hex_digest| hex::decode(hex_digest).ok())
+        .ok_or_else(|| SlackError::SignatureError("Malformed signature header".to_string()))?;
+
+    new_mac(signing_secret, timestamp, body)
+        .verify_slice(&expected)
+        .map_err(|_| SlackError::SignatureError("Signature mismatch".to_string()))
+}
+
+fn new_mac(signing_secret: &str, timestamp: &str, body: &[u8]) -> HmacSha256 {
+    // HMAC accepts keys of any length, so this cannot fail
+    let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
+        .expect("HMAC can take a key of any size");
+    mac.update(SIGNATURE_VERSION.as_bytes());
+    mac.update(b":");
+    mac.update(timestamp.as_bytes());
+    mac.update(b":");
+    mac.update(body);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+    const BODY: &[u8] = b"token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&command=%2Fslaist";
+
+    #[test]
+    fn test_verify_valid_signature() {
+        let signature = compute_signature(SECRET, "1531420618", BODY);
+        assert!(signature.starts_with("v0="));
+        assert!(verify_signature_at(SECRET, "1531420618", BODY, &signature, 1531420618).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_body() {
+        let signature = compute_signature(SECRET, "1531420618", BODY);
+        let result = verify_signature_at(
+            SECRET,
+            "1531420618",
+            b"token=tampered",
+            &signature,
+            1531420618,
+        );
+        assert!(matches!(result, Err(SlackError::SignatureError(_))));
+    }
+
+    #[test]
+    fn test_verify_rejects_stale_timestamp() {
+        let signature = compute_signature(SECRET, "1531420618", BODY);
+        let now = 1531420618 + MAX_REQUEST_AGE_SECS + 1;
+        let result = verify_signature_at(SECRET, "1531420618", BODY, &signature, now);
+        assert!(matches!(result, Err(SlackError::SignatureError(_))));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_header() {
+        for signature in ["", "v0=not-hex", "v1=abcdef", "abcdef"] {
+            let result = verify_signature_at(SECRET, "1531420618", BODY, signature, 1531420618);
+            assert!(matches!(result, Err(SlackError::SignatureError(_))));
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{
+    CommandResponse, EventCallback, InteractionPayload, RetryPolicy, SLACK_API_URL, SlackError,
+    SlashCommand, send_request,
+};
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client;
+use serde::Deserialize;
+use std::future::Future;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Longest wait between reconnection attempts after a failed connection
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Receives what Slack sends over a Socket Mode connection
+///
+/// Every envelope is acknowledged by the client, so implementations only need
+/// to handle the payloads they care about.
+pub trait SocketModeHandler: Send + Sync {
+    /// Called for interactive payloads, e.g. a ticked checkbox
+    fn on_interaction(&self, _payload: InteractionPayload) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called for slash commands; the returned response is sent back with the acknowledgement
+    fn on_slash_command(
+        &self,
+        _command: SlashCommand,
+    ) -> impl Future<Output = Option<CommandResponse>> + Send {
+        async { None }
+    }
+
+    /// Called for Events API callbacks
+    fn on_event(&self, _callback: EventCallback) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+}
+
+/// Why a Socket Mode connection ended without an error
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionEnd {
+    /// Slack asked us to reconnect, e.g. because the connection is being refreshed
+    Disconnect(String),
+    /// The app's Socket Mode connection was disabled and should not be re-opened
+    LinkDisabled,
+    /// The WebSocket was closed
+    Closed,
+}
+
+/// A message received over the Socket Mode WebSocket
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    envelope_id: Option<String>,
+    #[serde(default)]
+    payload: Option<serde_json::Value>,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// A client for Slack's Socket Mode, for workspaces that can't expose a public HTTP endpoint
+#[derive(Debug, Clone)]
+pub struct SocketModeClient {
+    client: Client,
+    app_token: String,
+}
+
+impl SocketModeClient {
+    /// Create a new Socket Mode client with an app-level token (starts with "xapp-")
+    pub fn with_app_token(app_token: String) -> Result<Self, SlackError> {
+        if app_token.is_empty() {
+            return Err(SlackError::ConfigError(
+                "App token cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(SocketModeClient {
+            client: Client::new(),
+            app_token,
+        })
+    }
+
+    /// Ask Slack for a WebSocket URL through `apps.connections.open`
+    pub async fn open_connection(&self) -> Result<String, SlackError> {
+        let request = self
+            .client
+            .post(format!("{}/apps.connections.open", SLACK_API_URL));
+        let api_response =
+            send_request(request, &self.app_token, &RetryPolicy::default(), true).await?;
+
+        api_response
+            .url
+            .ok_or_else(|| SlackError::InvalidResponse("No WebSocket URL in response".to_string()))
+    }
+
+    /// Keep a Socket Mode connection open, reconnecting whenever Slack asks for it
+    /// or the connection drops. Only returns when Slack disables the connection.
+    pub async fn run<H: SocketModeHandler>(&self, handler: &H) -> Result<(), SlackError> {
+        let mut delay = Duration::from_secs(1);
+
+        loop {
+            let result = match self.open_connection().await {
+                Ok(url) => run_connection(&url, handler).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(ConnectionEnd::LinkDisabled) => return Ok(()),
+                Ok(ConnectionEnd::Disconnect(reason)) => {
+                    println!("🔌 Slack requested a reconnect ({})", reason);
+                    delay = Duration::from_secs(1);
+                }
+                Ok(ConnectionEnd::Closed) => {
+                    println!("🔌 Socket Mode connection closed, reconnecting...");
+                    delay = Duration::from_secs(1);
+                }
+                Err(e) if e.code().is_some_and(|code| code.is_auth_error()) => return Err(e),
+                Err(e) => {
+                    eprintln!(
+                        "⚠️  Socket Mode connection failed: {} (retrying in {}s)",
+                        e,
+                        delay.as_secs()
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                }
+            }
+        }
+    }
+}
+
+/// Serve a single Socket Mode connection until it is closed or Slack asks to reconnect
+///
+/// # Arguments
+/// * `url` - WebSocket URL, as returned by `SocketModeClient::open_connection`
+/// * `handler` - Receives the payloads of all acknowledged envelopes
+pub async fn run_connection<H: SocketModeHandler>(
+    url: &str,
+    handler: &H,
+) -> Result<ConnectionEnd, SlackError> {
+    let (mut socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .map_err(|e| SlackError::WebSocketError(e.to_string()))?;
+
+    while let Some(message) = socket.next().await {
+        let message = message.map_err(|e| SlackError::WebSocketError(e.to_string()))?;
+
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => return Ok(ConnectionEnd::Closed),
+            // Pings are answered by tungstenite itself
+            _ => continue,
+        };
+
+        let envelope: Envelope = match serde_json::from_str(&text) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                eprintln!("⚠️  Ignoring malformed Socket Mode message: {}", e);
+                continue;
+            }
+        };
+
+        match envelope.kind.as_str() {
+            "hello" => continue,
+            "disconnect" => {
+                let reason = envelope.reason.unwrap_or_else(|| "unknown".to_string());
+                if reason == "link_disabled" {
+                    return Ok(ConnectionEnd::LinkDisabled);
+                }
+                return Ok(ConnectionEnd::Disconnect(reason));
+            }
+            _ => {}
+        }
+
+        let Some(envelope_id) = envelope.envelope_id else {
+            continue;
+        };
+        let payload = envelope.payload.unwrap_or_default();
+
+        match envelope.kind.as_str() {
+            "slash_commands" => {
+                // Slash command responses have to travel with the acknowledgement
+                let response = match serde_json::from_value::<SlashCommand>(payload) {
+                    Ok(command) => handler.on_slash_command(command).await,
+                    Err(e) => {
+                        eprintln!("⚠️  Ignoring malformed slash command: {}", e);
+                        None
+                    }
+                };
+                let mut ack = serde_json::json!({ "envelope_id": envelope_id });
+                if let Some(response) = response {
+                    ack["payload"] = serde_json::to_value(response)?;
+                }
+                send_ack(&mut socket, ack).await?;
+            }
+            "interactive" => {
+                send_ack(
+                    &mut socket,
+                    serde_json::json!({ "envelope_id": envelope_id }),
+                )
+                .await?;
+                match serde_json::from_value::<InteractionPayload>(payload) {
+                    Ok(interaction) => handler.on_interaction(interaction).await,
+                    Err(e) => eprintln!("⚠️  Ignoring malformed interaction: {}", e),
+                }
+            }
+            "events_api" => {
+                send_ack(
+                    &mut socket,
+                    serde_json::json!({ "envelope_id": envelope_id }),
+                )
+                .await?;
+                match serde_json::from_value::<EventCallback>(payload) {
+                    Ok(callback) => handler.on_event(callback).await,
+                    Err(e) => eprintln!("⚠️  Ignoring malformed event: {}", e),
+                }
+            }
+            _ => {
+                send_ack(
+                    &mut socket,
+                    serde_json::json!({ "envelope_id": envelope_id }),
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(ConnectionEnd::Closed)
+}
+
+async fn send_ack<S>(socket: &mut S, ack: serde_json::Value) -> Result<(), SlackError>
+where
+    S: SinkExt<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    socket
+        .send(Message::Text(ack.to_string().into()))
+        .await
+        .map_err(|e| SlackError::WebSocketError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl SocketModeHandler for RecordingHandler {
+        async fn on_interaction(&self, payload: InteractionPayload) {
+            if let InteractionPayload::BlockActions(actions) = payload {
+                self.calls.lock().unwrap().push(format!(
+                    "interaction:{}",
+                    actions.completed_task_ids().join(",")
+                ));
+            }
+        }
+
+        async fn on_slash_command(&self, command: SlashCommand) -> Option<CommandResponse> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("command:{}", command.text));
+            Some(CommandResponse::ephemeral("Got it"))
+        }
+
+        async fn on_event(&self, callback: EventCallback) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("event:{}", callback.event_id.unwrap_or_default()));
+        }
+    }
+
+    /// Start a local WebSocket server that plays the given messages and collects the replies
+    async fn stand_in(
+        messages: Vec<serde_json::Value>,
+    ) -> (String, tokio::task::JoinHandle<Vec<serde_json::Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let mut acks = Vec::new();
+
+            for message in messages {
+                let expects_ack = message.get("envelope_id").is_some();
+                socket
+                    .send(Message::Text(message.to_string().into()))
+                    .await
+                    .unwrap();
+                if expects_ack {
+                    if let Some(Ok(Message::Text(text))) = socket.next().await {
+                        acks.push(serde_json::from_str(&text).unwrap());
+                    }
+                }
+            }
+            acks
+        });
+
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn test_run_connection_acknowledges_envelopes() {
+        let (url, server) = stand_in(vec![
+            serde_json::json!({ "type": "hello", "num_connections": 1 }),
+            serde_json::json!({
+                "type": "slash_commands",
+                "envelope_id": "env-1",
+                "accepts_response_payload": true,
+                "payload": { "command": "/slaist", "text": "today", "user_id": "U1", "channel_id": "C1" }
+            }),
+            serde_json::json!({
+                "type": "interactive",
+                "envelope_id": "env-2",
+                "payload": {
+                    "type": "block_actions",
+                    "user": { "id": "U1" },
+                    "actions": [{ "action_id": "complete_task", "selected_options": [{ "value": "42" }] }]
+                }
+            }),
+            serde_json::json!({
+                "type": "events_api",
+                "envelope_id": "env-3",
+                "payload": {
+                    "type": "event_callback",
+                    "event_id": "Ev1",
+                    "event": { "type": "app_mention", "text": "<@U0BOT> hi", "channel": "C1", "ts": "1.2" }
+                }
+            }),
+            serde_json::json!({ "type": "disconnect", "reason": "refresh_requested" }),
+        ])
+        .await;
+
+        let handler = RecordingHandler::default();
+        let end = run_connection(&url, &handler).await.unwrap();
+        let acks = server.await.unwrap();
+
+        assert_eq!(
+            end,
+            ConnectionEnd::Disconnect("refresh_requested".to_string())
+        );
+        assert_eq!(
+            *handler.calls.lock().unwrap(),
+            vec!["command:today", "interaction:42", "event:Ev1"]
+        );
+
+        assert_eq!(acks.len(), 3);
+        assert_eq!(acks[0]["envelope_id"], "env-1");
+        assert_eq!(acks[0]["payload"]["text"], "Got it");
+        assert_eq!(acks[1], serde_json::json!({ "envelope_id": "env-2" }));
+        assert_eq!(acks[2], serde_json::json!({ "envelope_id": "env-3" }));
+    }
+
+    #[tokio::test]
+    async fn test_run_connection_stops_when_link_disabled() {
+        let (url, server) = stand_in(vec![
+            serde_json::json!({ "type": "disconnect", "reason": "link_disabled" }),
+        ])
+        .await;
+
+        let end = run_connection(&url, &RecordingHandler::default())
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        assert_eq!(end, ConnectionEnd::LinkDisabled);
+    }
+
+    #[tokio::test]
+    async fn test_run_connection_fails_without_server() {
+        let result = run_connection("ws://127.0.0.1:1", &RecordingHandler::default()).await;
+        assert!(matches!(result, Err(SlackError::WebSocketError(_))));
+    }
+
+    #[test]
+    fn test_socket_mode_client_with_empty_token() {
+        let result = SocketModeClient::with_app_token("".to_string());
+        assert!(matches!(result.unwrap_err(), SlackError::ConfigError(_)));
+    }
+}
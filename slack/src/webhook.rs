@@ -0,0 +1,185 @@
+use crate::mrkdwn::Formatting;
+use crate::{
+    ApiErrorCode, IconSet, RetryPolicy, SlackError, SlackMessage, blocks, retry_after_header,
+};
+use reqwest::Client;
+
+/// Posts messages through an incoming webhook instead of the Web API
+///
+/// Webhooks need no bot token, but they always post to the channel chosen when the
+/// webhook was created and can't update or delete messages afterwards.
+#[derive(Debug, Clone)]
+pub struct WebhookClient {
+    client: Client,
+    url: String,
+    retry_policy: RetryPolicy,
+    formatting: Formatting,
+}
+
+impl WebhookClient {
+    /// Create a client for the given incoming webhook URL
+    pub fn new(url: impl Into<String>) -> Result<Self, SlackError> {
+        let url = url.into();
+        if url.trim().is_empty() {
+            return Err(SlackError::ConfigError(
+                "Webhook URL cannot be empty".to_string(),
+            ));
+        }
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(SlackError::ConfigError(format!(
+                "Webhook URL must be an http(s) URL, got {}",
+                url
+            )));
+        }
+
+        Ok(WebhookClient {
+            client: Client::new(),
+            url,
+            retry_policy: RetryPolicy::default(),
+            formatting: Formatting::default(),
+        })
+    }
+
+    /// Use a different retry policy for transient failures
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Use different emoji for todos, priorities and labels
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.formatting.icons = icons;
+        self
+    }
+
+    /// Let task content mention these users, user groups or `here`/`channel`/`everyone`
+    pub fn with_allowed_mentions(mut self, allowed_mentions: Vec<String>) -> Self {
+        self.formatting.allowed_mentions = allowed_mentions;
+        self
+    }
+
+    /// Post a message to the webhook's channel
+    ///
+    /// Tasks are rendered without checkboxes, since there is no app to receive the clicks.
+    /// Only failures where Slack certainly didn't post the message are retried.
+    pub async fn send(&self, message: &SlackMessage) -> Result<(), SlackError> {
+        let body = serde_json::json!({
+            "text": blocks::fallback_text(message, &self.formatting),
+            "blocks": blocks::build_blocks(message, &self.formatting, false),
+        });
+        let mut attempt = 0;
+
+        loop {
+            match self.send_once(&body).await {
+                Err(e) if e.was_not_processed() && attempt < self.retry_policy.max_retries => {
+                    tokio::time::sleep(self.retry_policy.delay_for(attempt, &e)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_once(&self, body: &serde_json::Value) -> Result<(), SlackError> {
+        let response = self.client.post(&self.url).json(body).send().await?;
+        let status = response.status();
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(SlackError::RateLimited {
+                retry_after: retry_after_header(&response),
+            });
+        }
+        if status.is_success() {
+            return Ok(());
+        }
+
+        // Webhooks answer errors with a plain-text code like `channel_not_found`
+        let code = response.text().await.unwrap_or_default();
+        let code = code.trim();
+        if status.is_server_error() || code.is_empty() {
+            return Err(SlackError::ServerError(status.as_u16()));
+        }
+        Err(SlackError::ApiError(ApiErrorCode::from_code(code)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::stub_server;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    fn text_response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    fn test_client(url: &str) -> WebhookClient {
+        WebhookClient::new(url)
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+            })
+    }
+
+    #[test]
+    fn test_rejects_invalid_urls() {
+        assert!(matches!(
+            WebhookClient::new(""),
+            Err(SlackError::ConfigError(_))
+        ));
+        assert!(matches!(
+            WebhookClient::new("hooks.slack.com/services/T/B/X"),
+            Err(SlackError::ConfigError(_))
+        ));
+        assert!(WebhookClient::new("https://hooks.slack.com/services/T/B/X").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_rate_limits() {
+        let (url, requests) = stub_server(vec![
+            text_response("429 Too Many Requests", ""),
+            text_response("200 OK", "ok"),
+        ])
+        .await;
+
+        test_client(&url)
+            .send(&SlackMessage::new("- [ ] Task A"))
+            .await
+            .unwrap();
+
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_does_not_retry_server_errors() {
+        // The message may have been posted before the error, retrying could post it twice
+        let (url, requests) = stub_server(vec![text_response("503 Service Unavailable", "")]).await;
+
+        let result = test_client(&url).send(&SlackMessage::new("Hello")).await;
+
+        assert!(matches!(result, Err(SlackError::ServerError(503))));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_maps_error_codes() {
+        let (url, requests) =
+            stub_server(vec![text_response("404 Not Found", "channel_not_found")]).await;
+
+        let result = test_client(&url).send(&SlackMessage::new("Hello")).await;
+
+        assert!(matches!(
+            result,
+            Err(SlackError::ApiError(ApiErrorCode::ChannelNotFound))
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,4 +1,5 @@
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// A client for interacting with the Todoist API
@@ -9,7 +10,7 @@ pub struct TodoistClient {
 }
 
 /// Represents a Todoist task/todo item
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Todo {
     pub id: String,
     pub user_id: String,
@@ -37,7 +38,7 @@ pub struct Todo {
 }
 
 /// Represents due date information for a todo
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct TodoDue {
     pub date: String,
     pub is_recurring: bool,
@@ -46,6 +47,65 @@ pub struct TodoDue {
     pub timezone: Option<String>,
 }
 
+/// Represents a Todoist project
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub child_order: u32,
+}
+
+/// Represents a section within a Todoist project
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Section {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+}
+
+/// The user the API token belongs to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct User {
+    pub id: String,
+    #[serde(default)]
+    pub full_name: String,
+    #[serde(default)]
+    pub tz_info: Option<TimezoneInfo>,
+}
+
+/// Timezone the user set in Todoist
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimezoneInfo {
+    /// IANA name, e.g. "Europe/Amsterdam"
+    pub timezone: String,
+}
+
+/// Fields for creating a new task
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct NewTask {
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Natural language due date, e.g. "today" or "tomorrow at 10:00"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_string: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+/// One page of a list endpoint
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
 /// Error types for Todoist operations
 #[derive(Debug, thiserror::Error)]
 pub enum TodoistError {
@@ -84,7 +144,7 @@ impl TodoistClient {
     pub async fn get_all_todos(&self) -> Result<Vec<Todo>, TodoistError> {
         // If query is provided, use the filter endpoint
         if let Some(q) = &self.query {
-            return self.get_todos_by_filter(&q, None).await;
+            return self.get_todos_by_filter(q, None).await;
         }
 
         // Otherwise use the standard tasks endpoint
@@ -147,6 +207,109 @@ impl TodoistClient {
         let response_data: TodosResponse = response.json().await?;
         Ok(response_data.results)
     }
+
+    /// Fetches all projects of the user
+    pub async fn get_projects(&self) -> Result<Vec<Project>, TodoistError> {
+        self.get_all_pages("projects").await
+    }
+
+    /// Fetches the sections of all projects
+    pub async fn get_sections(&self) -> Result<Vec<Section>, TodoistError> {
+        self.get_all_pages("sections").await
+    }
+
+    /// Fetches every page of a list endpoint, following `next_cursor`
+    async fn get_all_pages<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>, TodoistError> {
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut url = format!("{}/{}?limit=200", self.base_url, path);
+            if let Some(cursor) = &cursor {
+                url.push_str(&format!("&cursor={}", urlencoding::encode(cursor)));
+            }
+
+            let response = self.client.get(&url).send().await?;
+
+            if !response.status().is_success() {
+                return Err(TodoistError::ApiError {
+                    status: response.status().as_u16(),
+                    message: response
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "Unknown error".to_string()),
+                });
+            }
+
+            let page: Page<T> = response.json().await?;
+            results.extend(page.results);
+            match page.next_cursor {
+                Some(next) if !next.is_empty() => cursor = Some(next),
+                _ => return Ok(results),
+            }
+        }
+    }
+
+    /// Fetches the user the API token belongs to
+    pub async fn get_user(&self) -> Result<User, TodoistError> {
+        let url = format!("{}/user", self.base_url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(TodoistError::ApiError {
+                status: response.status().as_u16(),
+                message: response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string()),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Creates a new task and returns it
+    pub async fn add_task(&self, task: &NewTask) -> Result<Todo, TodoistError> {
+        let url = format!("{}/tasks", self.base_url);
+
+        let response = self.client.post(&url).json(task).send().await?;
+
+        if !response.status().is_success() {
+            return Err(TodoistError::ApiError {
+                status: response.status().as_u16(),
+                message: response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string()),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Marks a task as completed
+    pub async fn close_task(&self, task_id: &str) -> Result<(), TodoistError> {
+        let url = format!(
+            "{}/tasks/{}/close",
+            self.base_url,
+            urlencoding::encode(task_id)
+        );
+
+        let response = self.client.post(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(TodoistError::ApiError {
+                status: response.status().as_u16(),
+                message: response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string()),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 // Helper function for creating a client - useful for testing
@@ -159,6 +322,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_client_creation() {
         let token = "test_token".to_string();
         let _client = TodoistClient::new(token, None);
@@ -173,6 +337,56 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_new_task_serialization_skips_empty_fields() {
+        let task = NewTask {
+            content: "Write docs".to_string(),
+            due_string: Some("today".to_string()),
+            ..NewTask::default()
+        };
+
+        let json = serde_json::to_value(&task).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "content": "Write docs", "due_string": "today" })
+        );
+    }
+
+    #[test]
+    fn test_user_deserialization() {
+        let user: User = serde_json::from_value(serde_json::json!({
+            "id": "123",
+            "full_name": "Jane Doe",
+            "email": "jane@example.com",
+            "tz_info": {
+                "timezone": "Europe/Amsterdam",
+                "gmt_string": "+02:00",
+                "hours": 2,
+                "minutes": 0,
+                "is_dst": 1
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(user.full_name, "Jane Doe");
+        assert_eq!(user.tz_info.unwrap().timezone, "Europe/Amsterdam");
+    }
+
+    #[test]
+    fn test_page_deserialization() {
+        let page: Page<Section> = serde_json::from_value(serde_json::json!({
+            "results": [{ "id": "1", "project_id": "2", "name": "Planning" }],
+            "next_cursor": "abc"
+        }))
+        .unwrap();
+        assert_eq!(page.results[0].name, "Planning");
+        assert_eq!(page.next_cursor.as_deref(), Some("abc"));
+
+        let last: Page<Section> =
+            serde_json::from_value(serde_json::json!({ "results": [] })).unwrap();
+        assert_eq!(last.next_cursor, None);
+    }
+
     #[test]
     fn test_date_range_formatting() {
         // Test that we can format date ranges correctly for the API
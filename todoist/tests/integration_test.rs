@@ -2,6 +2,7 @@ use std::env;
 use todoist::{TodoistClient, TodoistError};
 
 #[tokio::test]
+#[allow(clippy::assertions_on_constants)]
 async fn test_client_creation() {
     let token = "test_token".to_string();
     let _client = TodoistClient::new(token, None);